@@ -75,6 +75,11 @@ fn map_error(err: &Error) -> c_uint {
         IniParsingError { .. } => LIBLO_ERROR_FILE_PARSE_FAIL,
         VdfParsingError(_, _) => LIBLO_ERROR_FILE_PARSE_FAIL,
         SystemError(_, _) => LIBLO_ERROR_SYSTEM_ERROR,
+        IncompatibleLoadOrderMethod { .. } => LIBLO_ERROR_INVALID_ARGS,
+        SerializationError(_) => LIBLO_ERROR_TEXT_ENCODE_FAIL,
+        GameIdMismatch { .. } => LIBLO_ERROR_INVALID_ARGS,
+        CircularMasterDependency(_) => LIBLO_ERROR_INVALID_ARGS,
+        ActivePluginsFileLocked(_) => LIBLO_ERROR_IO_PERMISSION_DENIED,
     }
 }
 
@@ -27,14 +27,49 @@ use std::string::FromUtf8Error;
 use std::time;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LoadOrderMethod {
     Timestamp,
     Textfile,
     Asterisk,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PluginType {
+    Master,
+    LightMaster,
+    MediumMaster,
+    Normal,
+    Override,
+}
+
+/// Whether a plugin is active, inactive or not in the load order at all.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActivationState {
+    Active,
+    Inactive,
+    NotPresent,
+}
+
+/// What to do when saving a load order that has more active plugins than
+/// the game can load.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExcessActivePluginsAction {
+    /// Save the active plugins as they are.
+    #[default]
+    Keep,
+    /// Deactivate the excess plugins before saving.
+    Deactivate,
+    /// Fail with a TooManyActivePlugins error instead of saving.
+    Reject,
+}
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameId {
     Morrowind = 1,
     Oblivion,
@@ -116,6 +151,20 @@ pub enum Error {
     },
     VdfParsingError(PathBuf, String),
     SystemError(i32, OsString),
+    IncompatibleLoadOrderMethod {
+        game_id: GameId,
+        method: LoadOrderMethod,
+    },
+    SerializationError(String),
+    GameIdMismatch {
+        expected: GameId,
+        actual: GameId,
+    },
+    /// The names of plugins that depend on each other through their masters,
+    /// with each plugin having the next as a master and the last having the
+    /// first as a master.
+    CircularMasterDependency(Vec<String>),
+    ActivePluginsFileLocked(PathBuf),
 }
 
 impl From<time::SystemTimeError> for Error {
@@ -185,6 +234,16 @@ impl fmt::Display for Error {
                 write!(f, "Failed to parse VDF file at {path:?}: {message}"),
             Error::SystemError(code, message) =>
                 write!(f, "Error returned by the operating system, code {code}: {message:?}"),
+            Error::IncompatibleLoadOrderMethod { game_id, method } =>
+                write!(f, "The {method:?} load order method cannot be used with {game_id:?}"),
+            Error::SerializationError(message) =>
+                write!(f, "Failed to serialize the load order: {message}"),
+            Error::GameIdMismatch { expected, actual } =>
+                write!(f, "Expected game settings for {expected:?}, got settings for {actual:?}"),
+            Error::CircularMasterDependency(cycle) =>
+                write!(f, "The plugins {} depend on each other through their masters", cycle.join(", ")),
+            Error::ActivePluginsFileLocked(path) =>
+                write!(f, "The active plugins file at {path:?} is in use by another process"),
         }
     }
 }
@@ -22,7 +22,7 @@ use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::path::PathBuf;
 
-use crate::enums::{Error, GameId, LoadOrderMethod};
+use crate::enums::{Error, ExcessActivePluginsAction, GameId, LoadOrderMethod};
 use crate::ini::{test_files, use_my_games_directory};
 use crate::is_enderal;
 use crate::load_order::{
@@ -30,6 +30,8 @@ use crate::load_order::{
 };
 use crate::plugin::Plugin;
 
+const DEFAULT_MAX_BACKUPS: usize = 5;
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct GameSettings {
     id: GameId,
@@ -40,6 +42,13 @@ pub struct GameSettings {
     implicitly_active_plugins: Vec<String>,
     early_loading_plugins: Vec<String>,
     additional_plugins_directories: Vec<PathBuf>,
+    blocked_plugins: Vec<String>,
+    strict_active_limit: bool,
+    excess_active_plugins_on_save: ExcessActivePluginsAction,
+    max_backups: usize,
+    master_file_path: Option<PathBuf>,
+    cache_plugin_listing: bool,
+    plugin_listing_generation: u64,
 }
 
 const SKYRIM_HARDCODED_PLUGINS: &[&str] = &["Skyrim.esm"];
@@ -85,6 +94,23 @@ pub(crate) const STARFIELD_IMPLICITLY_ACTIVE_PLUGINS: &[&str] = &[
     "SFBGS008.esm",
 ];
 
+// The order in which Fallout: New Vegas' DLC plugins are released and usually
+// loaded, which the Ultimate Edition's .nam files activate. Fallout 3's GOTY
+// DLC (Anchorage, ThePitt, BrokenSteel, PointLookout, Zeta) have no
+// equivalent: the game only activates them if they're listed in plugins.txt,
+// so they're not implicitly active and their order is left to the load order.
+const FALLOUT_NV_DLC_PLUGINS: &[&str] = &[
+    "DeadMoney.esm",
+    "HonestHearts.esm",
+    "OldWorldBlues.esm",
+    "LonesomeRoad.esm",
+    "GunRunnersArsenal.esm",
+    "ClassicPack.esm",
+    "MercenaryPack.esm",
+    "TribalPack.esm",
+    "CaravanPack.esm",
+];
+
 // It's safe to use relative paths like this because the Microsoft Store
 // version of Fallout 4 won't launch if a DLC is installed and its install
 // path changed (e.g. by renaming a directory), so the DLC plugins must be
@@ -168,6 +194,13 @@ impl GameSettings {
             implicitly_active_plugins,
             early_loading_plugins,
             additional_plugins_directories,
+            blocked_plugins: Vec::new(),
+            strict_active_limit: false,
+            excess_active_plugins_on_save: ExcessActivePluginsAction::default(),
+            max_backups: DEFAULT_MAX_BACKUPS,
+            master_file_path: None,
+            cache_plugin_listing: false,
+            plugin_listing_generation: 0,
         })
     }
 
@@ -247,7 +280,95 @@ impl GameSettings {
         self.additional_plugins_directories = paths;
     }
 
+    pub fn blocked_plugins(&self) -> &[String] {
+        &self.blocked_plugins
+    }
+
+    /// Set the filenames of plugins that should never be added to the load
+    /// order, even if they are installed.
+    pub fn set_blocked_plugins(&mut self, plugin_names: Vec<String>) {
+        self.blocked_plugins = plugin_names;
+    }
+
+    pub fn is_blocked(&self, plugin: &str) -> bool {
+        use unicase::eq;
+        self.blocked_plugins()
+            .iter()
+            .any(|p| eq(p.as_str(), plugin))
+    }
+
+    pub fn strict_active_limit(&self) -> bool {
+        self.strict_active_limit
+    }
+
+    /// If enabled, loading a load order that has more active plugins than
+    /// the game supports will fail instead of succeeding. Implicitly active
+    /// plugins never cause loading to fail.
+    pub fn set_strict_active_limit(&mut self, strict: bool) {
+        self.strict_active_limit = strict;
+    }
+
+    pub fn excess_active_plugins_on_save(&self) -> ExcessActivePluginsAction {
+        self.excess_active_plugins_on_save
+    }
+
+    /// Set what saving a load order does if it has more active plugins than
+    /// the game supports. By default they're saved as they are.
+    pub fn set_excess_active_plugins_on_save(&mut self, action: ExcessActivePluginsAction) {
+        self.excess_active_plugins_on_save = action;
+    }
+
+    pub fn max_backups(&self) -> usize {
+        self.max_backups
+    }
+
+    /// Set how many backups of each load order file save_with_backup() keeps.
+    /// If zero, save_with_backup() doesn't make any backups.
+    pub fn set_max_backups(&mut self, max_backups: usize) {
+        self.max_backups = max_backups;
+    }
+
+    pub fn master_file_path(&self) -> Option<&Path> {
+        self.master_file_path.as_deref()
+    }
+
+    /// Set the path to the game's master file, for when it isn't installed
+    /// directly in the plugins directory. The master file keeps its usual
+    /// filename, it's only looked for at the given path.
+    pub fn set_master_file_path(&mut self, path: Option<PathBuf>) {
+        self.master_file_path = path;
+    }
+
+    pub fn cache_plugin_listing(&self) -> bool {
+        self.cache_plugin_listing
+    }
+
+    /// If enabled, load orders reuse the filenames they found in the plugins
+    /// directories when loading again, until one of the directories changes
+    /// or invalidate_plugin_listing() is called. The listing itself is held
+    /// by each load order, so that these settings stay a plain value.
+    /// Caching is disabled by default.
+    pub fn set_cache_plugin_listing(&mut self, enabled: bool) {
+        self.cache_plugin_listing = enabled;
+    }
+
+    pub fn plugin_listing_generation(&self) -> u64 {
+        self.plugin_listing_generation
+    }
+
+    /// Make load orders rescan the plugins directories the next time they
+    /// load, even if the directories seem unchanged.
+    pub fn invalidate_plugin_listing(&mut self) {
+        self.plugin_listing_generation = self.plugin_listing_generation.wrapping_add(1);
+    }
+
     pub fn plugin_path(&self, plugin_name: &str) -> PathBuf {
+        if let Some(path) = &self.master_file_path {
+            if unicase::eq(plugin_name, self.master_file()) {
+                return path.clone();
+            }
+        }
+
         plugin_path(
             self.id,
             plugin_name,
@@ -474,12 +595,19 @@ fn plugins_file_path(
     game_path: &Path,
     local_path: &Path,
 ) -> Result<PathBuf, Error> {
-    match game_id {
-        GameId::Morrowind => Ok(game_path.join("Morrowind.ini")),
-        GameId::Oblivion => oblivion_plugins_file_path(game_path, local_path),
+    let path = match game_id {
+        GameId::Morrowind => game_path.join("Morrowind.ini"),
+        GameId::Oblivion => oblivion_plugins_file_path(game_path, local_path)?,
         // Although the launchers for Fallout 3, Fallout NV and Skyrim all create plugins.txt, the
         // games themselves read Plugins.txt.
-        _ => Ok(local_path.join(PLUGINS_TXT)),
+        _ => local_path.join(PLUGINS_TXT),
+    };
+
+    // The file may not exist yet, but if something is there it must be a file.
+    if path.is_dir() {
+        Err(Error::InvalidPath(path))
+    } else {
+        Ok(path)
     }
 }
 
@@ -607,7 +735,17 @@ fn implicitly_active_plugins(
         // If there is a .nam file with the same basename as a plugin then the plugin is activated
         // and listed as a DLC in the game's title screen menu. This only works in the game's
         // Data path, so ignore additional plugin directories.
-        let nam_plugins = find_nam_plugins(&game_path.join("Data"))?;
+        let mut nam_plugins = find_nam_plugins(&game_path.join("Data"))?;
+
+        // Directory iteration order is unspecified, so put the official DLC
+        // first in their usual order, followed by any other plugins by name.
+        nam_plugins.sort_by_cached_key(|p| {
+            let dlc_index = FALLOUT_NV_DLC_PLUGINS
+                .iter()
+                .position(|d| unicase::eq(*d, p.as_str()))
+                .unwrap_or(FALLOUT_NV_DLC_PLUGINS.len());
+            (dlc_index, p.to_lowercase())
+        });
 
         plugin_names.extend(nam_plugins);
     } else if game_id == GameId::Skyrim {
@@ -659,7 +797,10 @@ fn plugin_path(
 mod tests {
     #[cfg(windows)]
     use std::env;
-    use std::{fs::create_dir, io::Write};
+    use std::{
+        fs::{create_dir, create_dir_all},
+        io::Write,
+    };
     use tempfile::tempdir;
 
     use crate::tests::copy_to_dir;
@@ -1029,6 +1170,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_local_and_my_games_paths_should_error_if_the_active_plugins_file_path_is_a_directory() {
+        let tmp_dir = tempdir().unwrap();
+        let local_path = tmp_dir.path().join("local");
+        let plugins_file_path = local_path.join("Plugins.txt");
+        create_dir_all(&plugins_file_path).unwrap();
+
+        match GameSettings::with_local_and_my_games_paths(
+            GameId::SkyrimSE,
+            &tmp_dir.path().join("game"),
+            &local_path,
+            PathBuf::default(),
+        )
+        .unwrap_err()
+        {
+            Error::InvalidPath(path) => assert_eq!(plugins_file_path, path),
+            e => panic!("Expected invalid path error, got {:?}", e),
+        }
+    }
+
     #[test]
     fn early_loading_plugins_should_be_mapped_from_game_id() {
         let mut settings = game_with_generic_paths(GameId::Skyrim);
@@ -1153,6 +1314,37 @@ mod tests {
         assert_eq!(plugins, settings.early_loading_plugins());
     }
 
+    #[test]
+    fn implicitly_active_plugins_should_include_anniversary_edition_plugins_in_ccc_file_order() {
+        let tmp_dir = tempdir().unwrap();
+        let game_path = tmp_dir.path();
+
+        // Anniversary Edition's Skyrim.ccc lists its bundled Creation Club
+        // plugins, which aren't in alphabetical order.
+        let ccc_plugins = [
+            "ccQDRSSE001-SurvivalMode.esl",
+            "ccBGSSSE025-AdvDSGS.esm",
+            "ccBGSSSE037-Curios.esl",
+            "ccQDRSSE002-Firewood.esl",
+            "ccBGSSSE001-Fish.esm",
+            "ccBGSSSE016-Umbra.esm",
+        ];
+        let settings = game_with_ccc_plugins(GameId::SkyrimSE, game_path, &ccc_plugins);
+
+        let mut expected_plugins = vec![
+            "Skyrim.esm",
+            "Update.esm",
+            "Dawnguard.esm",
+            "HearthFires.esm",
+            "Dragonborn.esm",
+        ];
+        expected_plugins.extend(ccc_plugins);
+
+        assert_eq!(expected_plugins, settings.implicitly_active_plugins());
+        assert_eq!(expected_plugins, settings.early_loading_plugins());
+        assert!(settings.is_implicitly_active("ccbgssse025-advdsgs.esm"));
+    }
+
     #[test]
     fn early_loading_plugins_should_use_the_starfield_ccc_file_in_game_path() {
         let tmp_dir = tempdir().unwrap();
@@ -1392,6 +1584,61 @@ mod tests {
         assert_eq!(expected_plugins, plugins);
     }
 
+    #[test]
+    fn implicitly_active_plugins_should_list_fallout_nv_dlc_in_their_usual_order() {
+        let tmp_dir = tempdir().unwrap();
+        let game_path = tmp_dir.path();
+        let data_path = game_path.join("Data");
+
+        create_dir(&data_path).unwrap();
+        for name in [
+            "plugin1",
+            "TribalPack",
+            "OldWorldBlues",
+            "DeadMoney",
+            "HonestHearts",
+        ] {
+            File::create(data_path.join(format!("{}.nam", name))).unwrap();
+        }
+
+        let settings = game_with_game_path(GameId::FalloutNV, &game_path);
+        let plugins: Vec<_> = settings
+            .implicitly_active_plugins()
+            .iter()
+            .filter(|p| p.ends_with(".esm"))
+            .collect();
+
+        let expected_plugins = vec![
+            "DeadMoney.esm",
+            "HonestHearts.esm",
+            "OldWorldBlues.esm",
+            "TribalPack.esm",
+            "plugin1.esm",
+        ];
+        assert_eq!(expected_plugins, plugins);
+    }
+
+    #[test]
+    fn implicitly_active_plugins_should_not_include_fallout_3_dlc() {
+        let tmp_dir = tempdir().unwrap();
+        let game_path = tmp_dir.path();
+        let data_path = game_path.join("Data");
+
+        create_dir(&data_path).unwrap();
+        for name in [
+            "Anchorage.esm",
+            "ThePitt.esm",
+            "BrokenSteel.esm",
+            "PointLookout.esm",
+            "Zeta.esm",
+        ] {
+            File::create(data_path.join(name)).unwrap();
+        }
+
+        let settings = game_with_game_path(GameId::Fallout3, &game_path);
+        assert!(settings.implicitly_active_plugins().is_empty());
+    }
+
     #[test]
     fn implicitly_active_plugins_should_include_update_esm_for_skyrim() {
         let settings = game_with_generic_paths(GameId::Skyrim);
@@ -1470,6 +1717,16 @@ mod tests {
         assert!(settings.loads_early("dawnguard.esm"));
     }
 
+    #[test]
+    fn is_blocked_should_match_case_insensitively() {
+        let mut settings = game_with_generic_paths(GameId::SkyrimSE);
+        assert!(!settings.is_blocked("Blank.esm"));
+
+        settings.set_blocked_plugins(vec!["Blank.esm".into()]);
+        assert!(settings.is_blocked("blank.ESM"));
+        assert!(!settings.is_blocked("Blank.esp"));
+    }
+
     #[test]
     fn plugins_folder_should_be_a_child_of_the_game_path() {
         let settings = game_with_generic_paths(GameId::Skyrim);
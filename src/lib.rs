@@ -26,9 +26,18 @@ mod plugin;
 #[cfg(test)]
 mod tests;
 
-pub use crate::enums::{Error, GameId, LoadOrderMethod};
+pub use crate::enums::{
+    ActivationState, Error, ExcessActivePluginsAction, GameId, LoadOrderMethod, PluginType,
+};
 pub use crate::game_settings::GameSettings;
-pub use crate::load_order::{ReadableLoadOrder, WritableLoadOrder};
+#[cfg(feature = "serde")]
+pub use crate::load_order::SerializableLoadOrder;
+pub use crate::load_order::{
+    ActivePluginCounts, ActivePluginInfo, AsteriskBasedLoadOrder, PluginState, ReadableLoadOrder,
+    ReadableLoadOrderExt, ReadableSnapshot, TextfileBasedLoadOrder, TimestampBasedLoadOrder,
+    WritableLoadOrder,
+};
+pub use crate::plugin::{normalize_plugin_name, Plugin};
 
 fn is_enderal(game_path: &std::path::Path) -> bool {
     game_path.join("Enderal Launcher.exe").exists()
@@ -17,19 +17,25 @@
  * along with libloadorder. If not, see <http://www.gnu.org/licenses/>.
  */
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufWriter, Write};
 
 use unicase::UniCase;
 
-use super::mutable::{hoist_masters, read_plugin_names, MutableLoadOrder};
-use super::readable::{ReadableLoadOrder, ReadableLoadOrderBase};
+use super::mutable::{
+    hoist_masters, read_plugin_names, validate_no_circular_masters, MutableLoadOrder,
+    PluginListingCache,
+};
+use super::readable::{ActivePluginCounts, ReadableLoadOrder, ReadableLoadOrderBase};
 use super::strict_encode;
 use super::timestamp_based::save_load_order_using_timestamps;
 use super::writable::{
-    activate, add, create_parent_dirs, deactivate, remove, set_active_plugins, WritableLoadOrder,
+    activate, activate_all_possible, active_plugins_missing_from_load_order_file, add,
+    apply_sorted_order, check_active_plugin_limits, clear, deactivate, deactivate_plugins,
+    from_state, handle_excess_active_plugins, import_from, remove, resolve_hoisting,
+    save_as_method, save_with_backup, set_active_plugins, set_active_plugins_ordered,
+    set_active_plugins_reporting, swap_plugins, to_state, would_exceed_limit, write_active_plugins,
+    WritableLoadOrder,
 };
-use crate::enums::{Error, GameId};
+use crate::enums::{Error, GameId, LoadOrderMethod};
 use crate::game_settings::{GameSettings, STARFIELD_IMPLICITLY_ACTIVE_PLUGINS};
 use crate::plugin::{trim_dot_ghost, Plugin};
 
@@ -37,6 +43,7 @@ use crate::plugin::{trim_dot_ghost, Plugin};
 pub struct AsteriskBasedLoadOrder {
     game_settings: GameSettings,
     plugins: Vec<Plugin>,
+    plugin_listing_cache: PluginListingCache,
 }
 
 impl AsteriskBasedLoadOrder {
@@ -44,6 +51,17 @@ impl AsteriskBasedLoadOrder {
         Self {
             game_settings,
             plugins: Vec::new(),
+            plugin_listing_cache: PluginListingCache::default(),
+        }
+    }
+
+    /// Create a load order that holds the given plugins, in the given order,
+    /// without reading anything from the filesystem.
+    pub fn from_plugins(game_settings: GameSettings, plugins: Vec<Plugin>) -> Self {
+        Self {
+            game_settings,
+            plugins,
+            plugin_listing_cache: PluginListingCache::default(),
         }
     }
 
@@ -91,38 +109,23 @@ impl WritableLoadOrder for AsteriskBasedLoadOrder {
         self.plugins_mut().clear();
 
         let plugin_tuples = self.read_from_active_plugins_file()?;
-        let filenames = self.find_plugins();
+        let filenames = self.plugin_listing_cache.find_plugins(&self.game_settings);
 
         self.load_unique_plugins(plugin_tuples, filenames);
 
         self.add_implicitly_active_plugins()?;
 
+        validate_no_circular_masters(&self.plugins)?;
+
         hoist_masters(&mut self.plugins)?;
 
-        Ok(())
+        check_active_plugin_limits(self)
     }
 
     fn save(&mut self) -> Result<(), Error> {
-        let path = self.game_settings().active_plugins_file();
-        create_parent_dirs(path)?;
-
-        let file = File::create(path).map_err(|e| Error::IoError(path.clone(), e))?;
-        let mut writer = BufWriter::new(file);
-        for plugin in self.plugins() {
-            if self.game_settings().loads_early(plugin.name()) {
-                // Skip early loading plugins, but not implicitly active plugins
-                // as they may need load order positions defined.
-                continue;
-            }
+        handle_excess_active_plugins(self)?;
 
-            if plugin.is_active() {
-                write!(writer, "*").map_err(|e| Error::IoError(path.clone(), e))?;
-            }
-            writer
-                .write_all(&strict_encode(plugin.name())?)
-                .map_err(|e| Error::IoError(path.clone(), e))?;
-            writeln!(writer).map_err(|e| Error::IoError(path.clone(), e))?;
-        }
+        save_using_asterisks(self)?;
 
         if self.ignore_active_plugins_file() {
             // If the active plugins file is being ignored there's no harm in
@@ -135,6 +138,18 @@ impl WritableLoadOrder for AsteriskBasedLoadOrder {
         Ok(())
     }
 
+    fn save_with_backup(&mut self) -> Result<(), Error> {
+        save_with_backup(self)
+    }
+
+    fn save_as_method(&mut self, method: LoadOrderMethod) -> Result<(), Error> {
+        save_as_method(self, method)
+    }
+
+    fn serialize_active_plugins(&self) -> Result<Vec<u8>, Error> {
+        serialize_active_plugins(self)
+    }
+
     fn add(&mut self, plugin_name: &str) -> Result<usize, Error> {
         add(self, plugin_name)
     }
@@ -147,10 +162,34 @@ impl WritableLoadOrder for AsteriskBasedLoadOrder {
         self.replace_plugins(plugin_names)
     }
 
+    fn import_from(&mut self, plugin_names: &[&str]) -> Result<Vec<String>, Error> {
+        import_from(self, plugin_names)
+    }
+
+    fn validate_load_order(&self, plugin_names: &[&str]) -> Result<(), Error> {
+        self.validate_plugin_names(plugin_names).map(|_| ())
+    }
+
+    fn apply_sorted_order(&mut self, sorted_plugin_names: &[&str]) -> Result<(), Error> {
+        apply_sorted_order(self, sorted_plugin_names)
+    }
+
     fn set_plugin_index(&mut self, plugin_name: &str, position: usize) -> Result<usize, Error> {
         MutableLoadOrder::set_plugin_index(self, plugin_name, position)
     }
 
+    fn clear(&mut self) -> Result<(), Error> {
+        clear(self)
+    }
+
+    fn resolve_hoisting(&mut self) -> Result<(), Error> {
+        resolve_hoisting(self)
+    }
+
+    fn swap_plugins(&mut self, plugin_name_a: &str, plugin_name_b: &str) -> Result<(), Error> {
+        swap_plugins(self, plugin_name_a, plugin_name_b)
+    }
+
     fn is_self_consistent(&self) -> Result<bool, Error> {
         Ok(true)
     }
@@ -186,6 +225,14 @@ impl WritableLoadOrder for AsteriskBasedLoadOrder {
         Ok(!plugins_listed)
     }
 
+    fn active_plugins_file_method_mismatch(&self) -> Option<LoadOrderMethod> {
+        None
+    }
+
+    fn active_plugins_missing_from_load_order_file(&self) -> Result<Vec<String>, Error> {
+        active_plugins_missing_from_load_order_file(self)
+    }
+
     fn activate(&mut self, plugin_name: &str) -> Result<(), Error> {
         activate(self, plugin_name)
     }
@@ -194,25 +241,93 @@ impl WritableLoadOrder for AsteriskBasedLoadOrder {
         deactivate(self, plugin_name)
     }
 
+    fn deactivate_plugins(&mut self, plugin_names: &[&str]) -> Result<(), Error> {
+        deactivate_plugins(self, plugin_names)
+    }
+
+    fn activate_all_possible(&mut self) -> Result<Vec<String>, Error> {
+        activate_all_possible(self)
+    }
+
+    fn would_exceed_limit(&self, plugin_name: &str) -> Result<bool, Error> {
+        would_exceed_limit(self, plugin_name)
+    }
+
     fn set_active_plugins(&mut self, active_plugin_names: &[&str]) -> Result<(), Error> {
         set_active_plugins(self, active_plugin_names)
     }
+
+    fn set_active_plugins_ordered(&mut self, active_plugin_names: &[&str]) -> Result<(), Error> {
+        set_active_plugins_ordered(self, active_plugin_names)
+    }
+
+    fn set_active_plugins_reporting(
+        &mut self,
+        active_plugin_names: &[&str],
+    ) -> Result<ActivePluginCounts, Error> {
+        set_active_plugins_reporting(self, active_plugin_names)
+    }
+
+    fn to_state(&self) -> Vec<(String, bool)> {
+        to_state(self)
+    }
+
+    fn from_state(&mut self, state: &[(String, bool)]) -> Result<(), Error> {
+        from_state(self, state)
+    }
+}
+
+pub fn save_using_asterisks<T: ReadableLoadOrderBase>(load_order: &T) -> Result<(), Error> {
+    let content = serialize_active_plugins(load_order)?;
+
+    write_active_plugins(load_order.game_settings().active_plugins_file(), &content)
+}
+
+pub fn serialize_active_plugins<T: ReadableLoadOrderBase>(
+    load_order: &T,
+) -> Result<Vec<u8>, Error> {
+    let mut content = Vec::new();
+
+    for plugin in load_order.plugins() {
+        if load_order.game_settings().loads_early(plugin.name()) {
+            // Skip early loading plugins, but not implicitly active plugins
+            // as they may need load order positions defined.
+            continue;
+        }
+
+        if plugin.is_active() {
+            content.push(b'*');
+        }
+        content.extend_from_slice(&strict_encode(plugin.name())?);
+        content.push(b'\n');
+    }
+
+    Ok(content)
 }
 
 fn plugin_line_mapper(line: &str) -> Option<(&str, bool)> {
     if line.is_empty() || line.starts_with('#') {
         None
     } else if line.as_bytes()[0] == b'*' {
-        Some((&line[1..], true))
+        Some((line[1..].trim_start(), true))
     } else {
         Some((line, false))
     }
 }
 
-fn owning_plugin_line_mapper(line: &str) -> Option<(String, bool)> {
+pub fn owning_plugin_line_mapper(line: &str) -> Option<(String, bool)> {
     plugin_line_mapper(line).map(|(name, active)| (name.to_owned(), active))
 }
 
+/// Get the name of the plugin that a line of an asterisk-based active plugins
+/// file activates, if any.
+pub fn active_plugin_name_line_mapper(line: &str) -> Option<String> {
+    match plugin_line_mapper(line) {
+        Some((name, true)) => Some(name.to_owned()),
+        _ => None,
+    }
+}
+
 fn ignore_active_plugins_file_fallout4(game_settings: &GameSettings) -> bool {
     // The implicitly active plugins are the early loading plugins plus test file plugins.
     matches!(game_settings.id(), GameId::Fallout4 | GameId::Fallout4VR)
@@ -233,8 +348,11 @@ fn ignore_active_plugins_file_starfield(game_settings: &GameSettings) -> bool {
 mod tests {
     use super::*;
 
-    use crate::enums::GameId;
+    use crate::enums::{ExcessActivePluginsAction, GameId};
+    use crate::load_order::readable::ReadableLoadOrderExt;
     use crate::load_order::tests::*;
+    use crate::load_order::writable::create_parent_dirs;
+    use crate::load_order::TextfileBasedLoadOrder;
     use crate::tests::{copy_to_dir, copy_to_test_dir};
     use std::fs::{create_dir_all, remove_dir_all, File};
     use std::io;
@@ -247,6 +365,7 @@ mod tests {
         AsteriskBasedLoadOrder {
             game_settings,
             plugins,
+            plugin_listing_cache: PluginListingCache::default(),
         }
     }
 
@@ -491,6 +610,24 @@ mod tests {
         assert_eq!(expected_filenames, load_order.plugin_names());
     }
 
+    #[test]
+    fn load_should_trim_whitespace_and_carriage_returns_in_active_plugins_file() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &["Blàñk.esp \r", " Blank.esm\r\r"],
+        );
+
+        load_order.load().unwrap();
+
+        assert_eq!(
+            vec!["Skyrim.esm", "Blank.esm", "Blàñk.esp"],
+            load_order.active_plugin_names()
+        );
+    }
+
     #[test]
     fn load_should_ignore_active_plugins_file_lines_starting_with_a_hash() {
         let tmp_dir = tempdir().unwrap();
@@ -559,6 +696,316 @@ mod tests {
         assert!(load_order.index_of("Blàñk.esp").is_some());
     }
 
+    #[test]
+    fn load_should_not_add_blocked_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        write_active_plugins_file(load_order.game_settings(), &["*Blank.esm"]);
+        load_order
+            .game_settings_mut()
+            .set_blocked_plugins(vec!["blank.esm".into(), "Blàñk.esp".into()]);
+
+        load_order.load().unwrap();
+
+        assert!(load_order.index_of("Blank.esm").is_none());
+        assert!(load_order.index_of("Blàñk.esp").is_none());
+        assert!(load_order.index_of("Blank.esp").is_some());
+    }
+
+    #[test]
+    fn load_should_rescan_a_cached_plugin_listing_if_a_plugin_is_added() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        load_order
+            .game_settings_mut()
+            .set_cache_plugin_listing(true);
+        load_order.load().unwrap();
+
+        assert!(load_order.index_of("Blank - Copy.esp").is_none());
+
+        copy_to_test_dir("Blank.esp", "Blank - Copy.esp", load_order.game_settings());
+        load_order.load().unwrap();
+
+        assert!(load_order.index_of("Blank - Copy.esp").is_some());
+    }
+
+    #[test]
+    fn load_should_find_a_new_plugin_after_the_cached_plugin_listing_is_invalidated() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        load_order
+            .game_settings_mut()
+            .set_cache_plugin_listing(true);
+        load_order.load().unwrap();
+
+        copy_to_test_dir("Blank.esp", "Blank - Copy.esp", load_order.game_settings());
+        load_order.game_settings_mut().invalidate_plugin_listing();
+        load_order.load().unwrap();
+
+        assert!(load_order.index_of("Blank - Copy.esp").is_some());
+    }
+
+    #[test]
+    fn load_should_find_a_relocated_master_file_and_load_it_first() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let master_file = load_order.game_settings().master_file();
+        let plugins_dir = load_order.game_settings().plugins_directory();
+        let relocated_dir = tmp_dir.path().join("relocated");
+        create_dir_all(&relocated_dir).unwrap();
+        std::fs::rename(
+            plugins_dir.join(master_file),
+            relocated_dir.join(master_file),
+        )
+        .unwrap();
+
+        load_order
+            .game_settings_mut()
+            .set_master_file_path(Some(relocated_dir.join(master_file)));
+        load_order.load().unwrap();
+
+        assert_eq!(master_file, load_order.plugins()[0].name());
+        assert!(load_order.is_active(master_file));
+        assert!(load_order.game_settings().is_implicitly_active(master_file));
+    }
+
+    #[test]
+    fn load_should_error_if_two_plugins_are_masters_of_each_other() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        write_plugin_with_masters(load_order.game_settings(), "A.esp", &["B.esp"]);
+        write_plugin_with_masters(load_order.game_settings(), "B.esp", &["A.esp"]);
+
+        match load_order.load().unwrap_err() {
+            Error::CircularMasterDependency(mut cycle) => {
+                cycle.sort();
+                assert_eq!(vec!["A.esp", "B.esp"], cycle);
+            }
+            e => panic!("Expected circular master dependency error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn load_should_error_if_three_plugins_form_a_cycle() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        write_plugin_with_masters(load_order.game_settings(), "A.esp", &["B.esp"]);
+        write_plugin_with_masters(load_order.game_settings(), "B.esp", &["C.esp"]);
+        write_plugin_with_masters(load_order.game_settings(), "C.esp", &["A.esp"]);
+
+        match load_order.load().unwrap_err() {
+            Error::CircularMasterDependency(mut cycle) => {
+                cycle.sort();
+                assert_eq!(vec!["A.esp", "B.esp", "C.esp"], cycle);
+            }
+            e => panic!("Expected circular master dependency error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn check_circular_masters_should_not_error_if_masters_form_a_chain() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        write_plugin_with_masters(load_order.game_settings(), "A.esp", &["B.esp"]);
+        write_plugin_with_masters(load_order.game_settings(), "B.esp", &["C.esp"]);
+        write_plugin_with_masters(load_order.game_settings(), "C.esp", &[]);
+        load_order.load().unwrap();
+
+        assert!(load_order.check_circular_masters().is_ok());
+    }
+
+    #[test]
+    fn load_should_recognise_plugin_file_extensions_case_insensitively() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        copy_to_test_dir("Blank.esp", "Blank - Upper.ESP", load_order.game_settings());
+        copy_to_test_dir("Blank.esm", "Blank - Mixed.Esl", load_order.game_settings());
+
+        load_order.load().unwrap();
+
+        assert!(load_order.index_of("Blank - Upper.ESP").is_some());
+        assert!(load_order
+            .light_master_plugin_names()
+            .contains(&"Blank - Mixed.Esl".to_string()));
+    }
+
+    fn write_too_many_active_plugins(load_order: &AsteriskBasedLoadOrder) -> Vec<String> {
+        let plugin_names: Vec<String> = (0..260).map(|i| format!("Blank{}.esp", i)).collect();
+        for plugin_name in &plugin_names {
+            copy_to_test_dir("Blank.esp", plugin_name, load_order.game_settings());
+        }
+
+        write_active_plugins_file(load_order.game_settings(), &plugin_names);
+
+        plugin_names
+    }
+
+    #[test]
+    fn load_should_keep_excess_active_plugins_active_by_default() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let plugin_names = write_too_many_active_plugins(&load_order);
+
+        load_order.load().unwrap();
+
+        assert!(plugin_names.iter().all(|n| load_order.is_active(n)));
+    }
+
+    #[test]
+    fn load_should_error_if_strict_and_too_many_plugins_are_active() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        write_too_many_active_plugins(&load_order);
+
+        load_order.game_settings_mut().set_strict_active_limit(true);
+
+        match load_order.load().unwrap_err() {
+            Error::TooManyActivePlugins {
+                light_count,
+                medium_count,
+                full_count,
+            } => {
+                assert_eq!(0, light_count);
+                assert_eq!(0, medium_count);
+                assert_eq!(261, full_count);
+            }
+            e => panic!("Expected too many active plugins error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn load_should_not_error_if_strict_and_active_plugins_are_within_the_limit() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        load_order.game_settings_mut().set_strict_active_limit(true);
+
+        assert!(load_order.load().is_ok());
+    }
+
+    #[test]
+    fn save_should_keep_excess_active_plugins_by_default() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        write_too_many_active_plugins(&load_order);
+        load_order.load().unwrap();
+
+        let excess_plugins = load_order.excess_active_plugins();
+        assert!(!excess_plugins.is_empty());
+
+        load_order.save().unwrap();
+
+        load_order.load().unwrap();
+        assert_eq!(excess_plugins, load_order.excess_active_plugins());
+    }
+
+    #[test]
+    fn save_as_method_should_write_a_textfile_based_load_order_that_skyrim_can_read() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        load_order.plugins.swap(1, 2);
+        load_order.activate("Blank - Different.esp").unwrap();
+        load_order.deactivate("Blank.esp").unwrap();
+
+        load_order
+            .save_as_method(LoadOrderMethod::Textfile)
+            .unwrap();
+
+        let mut textfile_load_order =
+            TextfileBasedLoadOrder::new(game_settings_for_test(GameId::Skyrim, &tmp_dir.path()));
+        textfile_load_order.load().unwrap();
+
+        // Installed plugins that weren't in the saved load order get added
+        // when loading, so ignore them.
+        let read_plugin_names: Vec<&str> = textfile_load_order
+            .plugin_names()
+            .into_iter()
+            .filter(|n| load_order.index_of(n).is_some())
+            .collect();
+        assert_eq!(load_order.plugin_names(), read_plugin_names);
+        assert!(textfile_load_order.is_active("Blank - Different.esp"));
+        assert!(!textfile_load_order.is_active("Blank.esp"));
+    }
+
+    #[test]
+    fn save_as_method_should_error_if_the_game_does_not_support_the_given_method() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Fallout4, &tmp_dir.path());
+
+        match load_order
+            .save_as_method(LoadOrderMethod::Textfile)
+            .unwrap_err()
+        {
+            Error::IncompatibleLoadOrderMethod { game_id, method } => {
+                assert_eq!(GameId::Fallout4, game_id);
+                assert_eq!(LoadOrderMethod::Textfile, method);
+            }
+            e => panic!("Expected incompatible load order method error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn save_should_error_without_writing_if_rejecting_excess_active_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        write_too_many_active_plugins(&load_order);
+        load_order.load().unwrap();
+
+        let active_plugins_file = load_order.game_settings().active_plugins_file().clone();
+        let previous_content = std::fs::read(&active_plugins_file).unwrap();
+
+        load_order
+            .game_settings_mut()
+            .set_excess_active_plugins_on_save(ExcessActivePluginsAction::Reject);
+
+        match load_order.save().unwrap_err() {
+            Error::TooManyActivePlugins { full_count, .. } => assert_eq!(261, full_count),
+            e => panic!("Expected too many active plugins error, got {:?}", e),
+        }
+
+        assert_eq!(
+            previous_content,
+            std::fs::read(&active_plugins_file).unwrap()
+        );
+    }
+
+    #[test]
+    fn save_should_deactivate_excess_active_plugins_if_configured_to() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let plugin_names = write_too_many_active_plugins(&load_order);
+        load_order.load().unwrap();
+
+        let excess_plugins = load_order.excess_active_plugins();
+        assert_eq!(6, excess_plugins.len());
+
+        load_order
+            .game_settings_mut()
+            .set_excess_active_plugins_on_save(ExcessActivePluginsAction::Deactivate);
+        load_order.save().unwrap();
+
+        assert!(load_order.excess_active_plugins().is_empty());
+        assert!(excess_plugins.iter().all(|n| !load_order.is_active(n)));
+        assert!(load_order.is_active(&plugin_names[0]));
+
+        load_order.load().unwrap();
+        assert!(load_order.excess_active_plugins().is_empty());
+    }
+
     #[test]
     fn load_should_recognise_light_master_plugins() {
         let tmp_dir = tempdir().unwrap();
@@ -582,6 +1029,49 @@ mod tests {
         assert!(load_order.is_active("Update.esm"));
     }
 
+    #[test]
+    fn load_should_put_early_loading_plugins_first_even_if_listed_later_in_active_plugins_file() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let dlc = [
+            "Update.esm",
+            "Dawnguard.esm",
+            "HearthFires.esm",
+            "Dragonborn.esm",
+        ];
+        for plugin in dlc {
+            copy_to_test_dir("Blank.esm", plugin, &load_order.game_settings());
+        }
+
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &[
+                "Blank.esm",
+                "Dragonborn.esm",
+                "Blank.esp",
+                "HearthFires.esm",
+                "Update.esm",
+            ],
+        );
+
+        load_order.load().unwrap();
+
+        let expected_filenames = vec![
+            "Skyrim.esm",
+            "Update.esm",
+            "Dawnguard.esm",
+            "HearthFires.esm",
+            "Dragonborn.esm",
+        ];
+        assert_eq!(expected_filenames, &load_order.plugin_names()[..5]);
+        assert_eq!(expected_filenames, &load_order.active_plugin_names()[..5]);
+        assert_eq!(
+            vec!["Blank.esm", "Blank.esp"],
+            &load_order.active_plugin_names()[5..]
+        );
+    }
+
     #[test]
     fn load_should_empty_the_load_order_if_the_plugins_directory_does_not_exist() {
         let tmp_dir = tempdir().unwrap();
@@ -706,6 +1196,36 @@ mod tests {
         assert_eq!(expected_filenames, load_order.plugin_names());
     }
 
+    #[test]
+    fn load_should_find_plugins_in_configured_additional_plugins_directories_once() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let extra_path = tmp_dir.path().join("Extra Data");
+        copy_to_dir(
+            "Blank.esp",
+            &extra_path,
+            "Blank - Extra.esp",
+            GameId::SkyrimSE,
+        );
+        copy_to_dir("Blank.esp", &extra_path, "blank.ESP", GameId::SkyrimSE);
+
+        load_order
+            .game_settings_mut()
+            .set_additional_plugins_directories(vec![extra_path]);
+        load_order.load().unwrap();
+
+        assert!(load_order.index_of("Blank - Extra.esp").is_some());
+        assert_eq!(
+            1,
+            load_order
+                .plugin_names()
+                .iter()
+                .filter(|n| n.eq_ignore_ascii_case("Blank.esp"))
+                .count()
+        );
+    }
+
     #[test]
     fn load_should_ignore_active_plugins_file_for_fallout4_when_test_files_are_configured() {
         let tmp_dir = tempdir().unwrap();
@@ -767,6 +1287,29 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(windows)]
+    fn save_should_error_if_the_active_plugins_file_is_locked_by_another_process() {
+        use std::os::windows::fs::OpenOptionsExt;
+
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let path = load_order.game_settings().active_plugins_file().clone();
+        create_dir_all(path.parent().unwrap()).unwrap();
+        let _file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .share_mode(0)
+            .open(&path)
+            .unwrap();
+
+        match load_order.save().unwrap_err() {
+            Error::ActivePluginsFileLocked(p) => assert_eq!(path, p),
+            e => panic!("Expected active plugins file locked error, got {:?}", e),
+        }
+    }
+
     #[test]
     fn save_should_write_unghosted_plugin_names() {
         let tmp_dir = tempdir().unwrap();
@@ -918,6 +1461,42 @@ mod tests {
         assert_eq!(original_timestamp, new_timestamp);
     }
 
+    #[test]
+    fn import_from_should_skip_plugins_that_are_not_installed() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let skipped = load_order
+            .import_from(&[
+                "Blank.esm",
+                "Dawnguard.esm",
+                "Blank - Different.esp",
+                "missing.esp",
+                "Blank.esp",
+            ])
+            .unwrap();
+
+        assert_eq!(vec!["Dawnguard.esm", "missing.esp"], skipped);
+        assert_eq!(
+            vec![
+                "Skyrim.esm",
+                "Blank.esm",
+                "Blank - Different.esp",
+                "Blank.esp"
+            ],
+            load_order.plugin_names()
+        );
+        assert!(load_order.is_active("Skyrim.esm"));
+    }
+
+    #[test]
+    fn game_master_index_should_be_zero_for_games_that_require_the_master_to_load_first() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        assert_eq!(Some(0), load_order.game_master_index());
+    }
+
     #[test]
     fn is_self_consistent_should_return_true() {
         let tmp_dir = tempdir().unwrap();
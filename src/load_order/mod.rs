@@ -20,6 +20,9 @@
 mod asterisk_based;
 mod mutable;
 mod readable;
+#[cfg(feature = "serde")]
+mod serializable;
+mod snapshot;
 #[cfg(test)]
 mod tests;
 mod textfile_based;
@@ -33,7 +36,12 @@ use encoding_rs::WINDOWS_1252;
 use super::enums::Error;
 
 pub use self::asterisk_based::AsteriskBasedLoadOrder;
-pub use self::readable::ReadableLoadOrder;
+pub use self::readable::{
+    ActivePluginCounts, ActivePluginInfo, PluginState, ReadableLoadOrder, ReadableLoadOrderExt,
+};
+#[cfg(feature = "serde")]
+pub use self::serializable::SerializableLoadOrder;
+pub use self::snapshot::ReadableSnapshot;
 pub use self::textfile_based::TextfileBasedLoadOrder;
 pub use self::timestamp_based::TimestampBasedLoadOrder;
 pub use self::writable::WritableLoadOrder;
@@ -19,58 +19,30 @@
 
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::OsString;
 use std::fs::read_dir;
 use std::mem;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use encoding_rs::WINDOWS_1252;
 use rayon::prelude::*;
 use unicase::{eq, UniCase};
 
-use super::readable::{ReadableLoadOrder, ReadableLoadOrderBase};
-use crate::enums::Error;
+use super::readable::{ReadableLoadOrder, ReadableLoadOrderBase, ReadableLoadOrderExt};
+use crate::enums::{Error, LoadOrderMethod};
 use crate::game_settings::GameSettings;
-use crate::plugin::{has_plugin_extension, trim_dot_ghost, Plugin};
+use crate::ghostable_path::GhostablePath;
+use crate::plugin::{has_plugin_extension, normalize_plugin_name, trim_dot_ghost, Plugin};
 use crate::GameId;
 
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
 pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
     fn plugins_mut(&mut self) -> &mut Vec<Plugin>;
 
     fn insert_position(&self, plugin: &Plugin) -> Option<usize> {
-        if self.plugins().is_empty() {
-            return None;
-        }
-
-        // A blueprint plugin may be listed as an early loader (e.g. in a CCC
-        // file) but it still loads as a normal blueprint plugin.
-        if !plugin.is_blueprint_master() {
-            let mut loaded_plugin_count = 0;
-            for plugin_name in self.game_settings().early_loading_plugins() {
-                if eq(plugin.name(), plugin_name) {
-                    return Some(loaded_plugin_count);
-                }
-
-                if self.index_of(plugin_name).is_some() {
-                    loaded_plugin_count += 1;
-                }
-            }
-        }
-
-        generic_insert_position(self.plugins(), plugin)
-    }
-
-    fn find_plugins(&self) -> Vec<String> {
-        // A game might store some plugins outside of its main plugins directory
-        // so look for those plugins. They override any of the same names that
-        // appear in the main plugins directory, so check for the additional
-        // paths first.
-        let mut directories = self
-            .game_settings()
-            .additional_plugins_directories()
-            .to_vec();
-        directories.push(self.game_settings().plugins_directory());
-
-        find_plugins_in_dirs(&directories, self.game_settings().id())
+        insert_position(self, plugin)
     }
 
     fn validate_index(&self, plugin: &Plugin, index: usize) -> Result<(), Error> {
@@ -79,6 +51,8 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
             // same scale, even non-masters.
             validate_blueprint_plugin_index(self.plugins(), plugin, index)
         } else {
+            self.validate_game_master_index(plugin, index)?;
+
             self.validate_early_loading_plugin_indexes(plugin.name(), index)?;
 
             if plugin.is_master_file() {
@@ -103,8 +77,9 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
 
     fn set_plugin_index(&mut self, plugin_name: &str, position: usize) -> Result<usize, Error> {
         if let Some(x) = self.index_of(plugin_name) {
-            if x == position {
-                return Ok(position);
+            let is_last = x + 1 == self.plugins().len();
+            if x == position || (is_last && position > x) {
+                return Ok(x);
             }
         }
 
@@ -125,7 +100,9 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
         }
     }
 
-    fn replace_plugins(&mut self, plugin_names: &[&str]) -> Result<(), Error> {
+    /// Check that the given plugin names form a valid load order, and if so
+    /// return the plugins that they correspond to.
+    fn validate_plugin_names(&self, plugin_names: &[&str]) -> Result<Vec<Plugin>, Error> {
         let mut unique_plugin_names = HashSet::new();
 
         let non_unique_plugin = plugin_names
@@ -136,10 +113,27 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
             return Err(Error::DuplicatePlugin(n.to_string()));
         }
 
-        let mut plugins = map_to_plugins(self, plugin_names)?;
+        let plugins = map_to_plugins(self, plugin_names)?;
+
+        validate_game_master_position(&plugins, self.game_settings())?;
 
         validate_load_order(&plugins, self.game_settings().early_loading_plugins())?;
 
+        Ok(plugins)
+    }
+
+    fn replace_plugins(&mut self, plugin_names: &[&str]) -> Result<(), Error> {
+        let is_unchanged = plugin_names.len() == self.plugins().len()
+            && plugin_names
+                .iter()
+                .zip(self.plugins())
+                .all(|(n, p)| p.name_matches(n));
+        if is_unchanged {
+            return Ok(());
+        }
+
+        let mut plugins = self.validate_plugin_names(plugin_names)?;
+
         mem::swap(&mut plugins, self.plugins_mut());
 
         Ok(())
@@ -152,6 +146,7 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
     ) {
         let plugins: Vec<_> = remove_duplicates_icase(plugin_name_tuples, installed_filenames)
             .into_par_iter()
+            .filter(|(filename, _)| !self.game_settings().is_blocked(trim_dot_ghost(filename)))
             .filter_map(|(filename, active)| {
                 Plugin::with_active(&filename, self.game_settings(), active).ok()
             })
@@ -172,6 +167,26 @@ pub trait MutableLoadOrder: ReadableLoadOrder + ReadableLoadOrderBase + Sync {
         Ok(())
     }
 
+    /// Check that the given plugin and index won't cause the game's master
+    /// file to load anywhere but first, for games that require it to.
+    fn validate_game_master_index(&self, plugin: &Plugin, index: usize) -> Result<(), Error> {
+        let game_settings = self.game_settings();
+        if !game_master_must_load_first(game_settings) {
+            return Ok(());
+        }
+
+        let master_file = game_settings.master_file();
+        let is_game_master = plugin.name_matches(master_file);
+
+        if (is_game_master && index != 0)
+            || (!is_game_master && index == 0 && self.index_of(master_file).is_some())
+        {
+            Err(Error::GameMasterMustLoadFirst(master_file.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Check that the given plugin and index won't cause any early-loading
     /// plugins to load in the wrong positions.
     fn validate_early_loading_plugin_indexes(
@@ -260,13 +275,23 @@ where
     let content =
         std::fs::read(file_path).map_err(|e| Error::IoError(file_path.to_path_buf(), e))?;
 
+    // A text editor may have saved the file as UTF-8 with a BOM, which would
+    // otherwise be decoded as part of the first plugin's name.
+    let bytes = content.strip_prefix(UTF8_BOM).unwrap_or(&content);
+
     // This should never fail, as although Windows-1252 has a few unused bytes
     // they get mapped to C1 control characters.
     let decoded_content = WINDOWS_1252
-        .decode_without_bom_handling_and_without_replacement(&content)
+        .decode_without_bom_handling_and_without_replacement(bytes)
         .ok_or_else(|| Error::DecodeError(content.clone()))?;
 
-    Ok(decoded_content.lines().filter_map(line_mapper).collect())
+    // Lines may have been written with stray carriage returns or padding
+    // whitespace by a text editor, so trim them.
+    Ok(decoded_content
+        .lines()
+        .map(normalize_plugin_name)
+        .filter_map(line_mapper)
+        .collect())
 }
 
 pub fn plugin_line_mapper(line: &str) -> Option<String> {
@@ -277,6 +302,60 @@ pub fn plugin_line_mapper(line: &str) -> Option<String> {
     }
 }
 
+/// Check that no plugin in the load order depends on itself through its
+/// masters, as then there is no order in which the plugins in the cycle can
+/// all load after their masters.
+pub fn validate_no_circular_masters(plugins: &[Plugin]) -> Result<(), Error> {
+    let mut masters_map: HashMap<UniCase<&str>, Vec<String>> = HashMap::new();
+    for plugin in plugins {
+        masters_map.insert(UniCase::new(plugin.name()), plugin.masters()?);
+    }
+
+    let mut visited = HashSet::new();
+    for plugin in plugins {
+        let mut path = Vec::new();
+        if let Some(cycle) = find_master_cycle(plugin.name(), &masters_map, &mut path, &mut visited)
+        {
+            return Err(Error::CircularMasterDependency(cycle));
+        }
+    }
+
+    Ok(())
+}
+
+/// Depth-first search through the masters of the given plugin. The path holds
+/// the plugins that are currently being visited, so reaching one of them
+/// again means that the plugins from that point in the path form a cycle.
+fn find_master_cycle<'a>(
+    plugin_name: &'a str,
+    masters_map: &'a HashMap<UniCase<&str>, Vec<String>>,
+    path: &mut Vec<&'a str>,
+    visited: &mut HashSet<UniCase<&'a str>>,
+) -> Option<Vec<String>> {
+    if let Some(index) = path.iter().position(|p| eq(*p, plugin_name)) {
+        return Some(path[index..].iter().map(|p| p.to_string()).collect());
+    }
+
+    let key = UniCase::new(plugin_name);
+    if visited.contains(&key) {
+        return None;
+    }
+
+    // Masters that aren't in the load order can't be part of a cycle.
+    let masters = masters_map.get(&key)?;
+
+    path.push(plugin_name);
+    for master in masters {
+        if let Some(cycle) = find_master_cycle(master, masters_map, path, visited) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+
+    visited.insert(key);
+    None
+}
+
 /// If an ESM has a master that is lower down in the load order, the master will
 /// be loaded directly before the ESM instead of in its usual position. This
 /// function "hoists" such masters further up the load order to match that
@@ -369,6 +448,213 @@ fn generic_insert_position(plugins: &[Plugin], plugin: &Plugin) -> Option<usize>
     })
 }
 
+/// Get the position at which the given plugin would be inserted into the
+/// load order, or None if it would be appended.
+pub fn insert_position<T: ReadableLoadOrderExt + ?Sized>(
+    load_order: &T,
+    plugin: &Plugin,
+) -> Option<usize> {
+    if load_order.loaded_plugins().is_empty() {
+        return None;
+    }
+
+    // The game's master file is inserted ahead of all other plugins, even for
+    // games that allow it to be moved afterwards.
+    if plugin.name_matches(load_order.game_settings().master_file()) {
+        return Some(0);
+    }
+
+    // A blueprint plugin may be listed as an early loader (e.g. in a CCC
+    // file) but it still loads as a normal blueprint plugin.
+    if !plugin.is_blueprint_master() {
+        let mut loaded_plugin_count = 0;
+        for plugin_name in load_order.game_settings().early_loading_plugins() {
+            if eq(plugin.name(), plugin_name) {
+                return Some(loaded_plugin_count);
+            }
+
+            if load_order.index_of(plugin_name).is_some() {
+                loaded_plugin_count += 1;
+            }
+        }
+    }
+
+    generic_insert_position(load_order.loaded_plugins(), plugin)
+}
+
+/// Get the filenames of the plugin files that are installed for the game,
+/// excluding blocked plugins. The filenames are unvalidated and may include
+/// ghosted plugins' ".ghost" extensions.
+pub fn find_plugins(game_settings: &GameSettings) -> Vec<String> {
+    let directories = plugin_directories(game_settings);
+    let filenames = find_plugins_in_dirs(&directories, game_settings.id());
+
+    filter_found_plugins(game_settings, filenames)
+}
+
+fn plugin_directories(game_settings: &GameSettings) -> Vec<PathBuf> {
+    // A game might store some plugins outside of its main plugins directory
+    // so look for those plugins. They override any of the same names that
+    // appear in the main plugins directory, so check for the additional
+    // paths first.
+    let mut directories = game_settings.additional_plugins_directories().to_vec();
+    directories.push(game_settings.plugins_directory());
+    directories
+}
+
+fn filter_found_plugins(game_settings: &GameSettings, mut filenames: Vec<String>) -> Vec<String> {
+    add_relocated_master_file(game_settings, &mut filenames);
+
+    filenames
+        .into_iter()
+        .filter(|f| !game_settings.is_blocked(trim_dot_ghost(f)))
+        .collect()
+}
+
+/// A cache of the filenames found by scanning the plugins directories, so
+/// that loading again doesn't rescan them if they haven't changed. It's only
+/// used if the game settings enable it, and is discarded when they invalidate
+/// it. The cached filenames stay in the order given by their files'
+/// modification times when they were scanned, as changing a file's
+/// modification time doesn't cause a rescan.
+#[derive(Clone, Debug, Default)]
+pub struct PluginListingCache {
+    listing: Option<CachedPluginListing>,
+}
+
+#[derive(Clone, Debug)]
+struct CachedPluginListing {
+    generation: u64,
+    directories: Vec<DirectoryState>,
+    filenames: Vec<String>,
+}
+
+/// Some filesystems (e.g. FAT) record modification times so coarsely that a
+/// directory can change again without its modification time changing, if the
+/// changes are this close together.
+const MODIFICATION_TIME_RESOLUTION: Duration = Duration::from_secs(2);
+
+/// A listing is stale if any of its directories has changed. Adding, removing
+/// or renaming a file changes its directory's modification time, so that's
+/// usually all that needs checking. If the directory was modified too
+/// recently before it was scanned for its modification time to reveal later
+/// changes, its entry names are also recorded and compared.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DirectoryState {
+    path: PathBuf,
+    modification_time: Option<SystemTime>,
+    entry_names: Option<Vec<OsString>>,
+}
+
+impl DirectoryState {
+    fn new(path: &Path) -> Self {
+        let scan_time = SystemTime::now();
+        let modification_time = directory_modification_time(path);
+
+        let is_recently_modified = match modification_time {
+            Some(time) => scan_time
+                .duration_since(time)
+                .map(|d| d < MODIFICATION_TIME_RESOLUTION)
+                .unwrap_or(true),
+            None => true,
+        };
+
+        DirectoryState {
+            path: path.to_path_buf(),
+            modification_time,
+            entry_names: is_recently_modified.then(|| directory_entry_names(path)),
+        }
+    }
+
+    fn is_unchanged(&self) -> bool {
+        if directory_modification_time(&self.path) != self.modification_time {
+            return false;
+        }
+
+        match &self.entry_names {
+            Some(entry_names) => *entry_names == directory_entry_names(&self.path),
+            None => true,
+        }
+    }
+}
+
+fn directory_modification_time(path: &Path) -> Option<SystemTime> {
+    path.metadata().and_then(|m| m.modified()).ok()
+}
+
+fn directory_entry_names(path: &Path) -> Vec<OsString> {
+    let mut entry_names: Vec<_> = read_dir(path)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|e| e.file_name())
+        .collect();
+    entry_names.sort();
+    entry_names
+}
+
+impl PluginListingCache {
+    /// Like the find_plugins() free function, but reuses the cached listing
+    /// if caching is enabled and the plugins directories are unchanged.
+    pub fn find_plugins(&mut self, game_settings: &GameSettings) -> Vec<String> {
+        let directories = plugin_directories(game_settings);
+        let filenames = self.listing(game_settings, &directories, || {
+            find_plugins_in_dirs(&directories, game_settings.id())
+        });
+
+        filter_found_plugins(game_settings, filenames)
+    }
+
+    fn listing<F>(
+        &mut self,
+        game_settings: &GameSettings,
+        directories: &[PathBuf],
+        scan: F,
+    ) -> Vec<String>
+    where
+        F: FnOnce() -> Vec<String>,
+    {
+        if !game_settings.cache_plugin_listing() {
+            self.listing = None;
+            return scan();
+        }
+
+        let generation = game_settings.plugin_listing_generation();
+        if let Some(listing) = &self.listing {
+            let is_unchanged = listing.generation == generation
+                && listing.directories.iter().map(|d| &d.path).eq(directories)
+                && listing.directories.iter().all(DirectoryState::is_unchanged);
+            if is_unchanged {
+                return listing.filenames.clone();
+            }
+        }
+
+        // Record the directories' states before scanning them, so that any
+        // changes made during the scan cause a rescan next time.
+        let directory_states = directories.iter().map(|d| DirectoryState::new(d)).collect();
+        let filenames = scan();
+        self.listing = Some(CachedPluginListing {
+            generation,
+            directories: directory_states,
+            filenames: filenames.clone(),
+        });
+        filenames
+    }
+}
+
+/// If the game's master file has been relocated outside the plugins
+/// directories, it won't have been found when scanning them, so add it.
+fn add_relocated_master_file(game_settings: &GameSettings, filenames: &mut Vec<String>) {
+    let master_file = game_settings.master_file();
+    let is_relocated = game_settings
+        .master_file_path()
+        .is_some_and(|p| p.resolve_path().is_ok());
+
+    if is_relocated && !filenames.iter().any(|f| eq(trim_dot_ghost(f), master_file)) {
+        filenames.insert(0, master_file.to_string());
+    }
+}
+
 fn find_plugins_in_dirs(directories: &[PathBuf], game: GameId) -> Vec<String> {
     let mut dir_entries: Vec<_> = directories
         .iter()
@@ -622,7 +908,32 @@ fn get_plugin_to_insert_at<T: MutableLoadOrder + ?Sized>(
     }
 }
 
-fn validate_load_order(plugins: &[Plugin], early_loading_plugins: &[String]) -> Result<(), Error> {
+pub fn game_master_must_load_first(game_settings: &GameSettings) -> bool {
+    // Timestamp-based games don't treat their master file specially.
+    game_settings.load_order_method() != LoadOrderMethod::Timestamp
+}
+
+/// Check that if the game's master file is in the given plugins, it loads
+/// first, for games that require it to.
+pub fn validate_game_master_position(
+    plugins: &[Plugin],
+    game_settings: &GameSettings,
+) -> Result<(), Error> {
+    if !game_master_must_load_first(game_settings) {
+        return Ok(());
+    }
+
+    let master_file = game_settings.master_file();
+    match plugins.iter().position(|p| p.name_matches(master_file)) {
+        Some(index) if index != 0 => Err(Error::GameMasterMustLoadFirst(master_file.to_string())),
+        _ => Ok(()),
+    }
+}
+
+pub fn validate_load_order(
+    plugins: &[Plugin],
+    early_loading_plugins: &[String],
+) -> Result<(), Error> {
     validate_early_loader_positions(plugins, early_loading_plugins)?;
 
     validate_no_unhoisted_non_masters_before_masters(plugins)?;
@@ -679,7 +990,7 @@ fn validate_no_unhoisted_non_masters_before_masters(plugins: &[Plugin]) -> Resul
     Ok(())
 }
 
-fn validate_plugins_load_before_their_masters(plugins: &[Plugin]) -> Result<(), Error> {
+pub fn validate_plugins_load_before_their_masters(plugins: &[Plugin]) -> Result<(), Error> {
     let mut plugins_map: HashMap<UniCase<String>, &Plugin> = HashMap::new();
 
     for plugin in plugins.iter().rev() {
@@ -750,7 +1061,7 @@ fn activate_unvalidated<T: MutableLoadOrder + ?Sized>(
     }
 }
 
-fn find_first_non_master_position(plugins: &[Plugin]) -> Option<usize> {
+pub fn find_first_non_master_position(plugins: &[Plugin]) -> Option<usize> {
     plugins.iter().position(|p| !p.is_master_file())
 }
 
@@ -1436,17 +1747,20 @@ mod tests {
         let tmp_dir = tempdir().unwrap();
         let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
 
+        copy_to_test_dir("Blank.esm", "Update.esm", &load_order.game_settings());
+        load_order.set_plugin_index("Update.esm", 1).unwrap();
+
         let existing_filenames = to_owned(load_order.plugin_names());
 
-        match load_order.set_plugin_index("Blank.esp", 0).unwrap_err() {
+        match load_order.set_plugin_index("Blank.esp", 1).unwrap_err() {
             Error::InvalidEarlyLoadingPluginPosition {
                 name,
                 pos,
                 expected_pos,
             } => {
-                assert_eq!("Skyrim.esm", name);
-                assert_eq!(1, pos);
-                assert_eq!(0, expected_pos);
+                assert_eq!("Update.esm", name);
+                assert_eq!(2, pos);
+                assert_eq!(1, expected_pos);
             }
             e => panic!(
                 "Expected InvalidEarlyLoadingPluginPosition error, got {:?}",
@@ -1462,17 +1776,20 @@ mod tests {
         let tmp_dir = tempdir().unwrap();
         let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
 
+        copy_to_test_dir("Blank.esm", "Update.esm", &load_order.game_settings());
+        load_order.set_plugin_index("Update.esm", 1).unwrap();
+
         let existing_filenames = to_owned(load_order.plugin_names());
 
-        match load_order.set_plugin_index("Skyrim.esm", 1).unwrap_err() {
+        match load_order.set_plugin_index("Update.esm", 2).unwrap_err() {
             Error::InvalidEarlyLoadingPluginPosition {
                 name,
                 pos,
                 expected_pos,
             } => {
-                assert_eq!("Skyrim.esm", name);
-                assert_eq!(1, pos);
-                assert_eq!(0, expected_pos);
+                assert_eq!("Update.esm", name);
+                assert_eq!(2, pos);
+                assert_eq!(1, expected_pos);
             }
             e => panic!(
                 "Expected InvalidEarlyLoadingPluginPosition error, got {:?}",
@@ -1483,6 +1800,55 @@ mod tests {
         assert_eq!(existing_filenames, load_order.plugin_names());
     }
 
+    #[test]
+    fn set_plugin_index_should_error_if_moving_a_plugin_before_the_game_master() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+
+        match load_order.set_plugin_index("Blank.esp", 0).unwrap_err() {
+            Error::GameMasterMustLoadFirst(name) => assert_eq!("Skyrim.esm", name),
+            e => panic!("Expected GameMasterMustLoadFirst error, got {:?}", e),
+        };
+
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn set_plugin_index_should_error_if_moving_the_game_master_off_index_zero() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+
+        match load_order.set_plugin_index("Skyrim.esm", 1).unwrap_err() {
+            Error::GameMasterMustLoadFirst(name) => assert_eq!("Skyrim.esm", name),
+            e => panic!("Expected GameMasterMustLoadFirst error, got {:?}", e),
+        };
+
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn set_plugin_index_should_allow_moving_the_morrowind_master_off_index_zero() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        load_and_insert(&mut load_order, "Blank.esm");
+
+        assert_eq!(1, load_order.set_plugin_index("Morrowind.esm", 1).unwrap());
+        assert_eq!(
+            vec![
+                "Blank.esm",
+                "Morrowind.esm",
+                "Blank.esp",
+                "Blank - Different.esp"
+            ],
+            load_order.plugin_names()
+        );
+    }
+
     #[test]
     fn set_plugin_index_should_error_if_inserting_an_early_loader_to_the_wrong_position() {
         let tmp_dir = tempdir().unwrap();
@@ -1621,6 +1987,19 @@ mod tests {
         assert_eq!(num_plugins, load_order.plugins().len());
     }
 
+    #[test]
+    fn set_plugin_index_should_not_move_the_last_plugin_if_given_a_later_index() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let index = load_order
+            .set_plugin_index("Blank - Different.esp", 10)
+            .unwrap();
+        assert_eq!(2, index);
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
     #[test]
     fn set_plugin_index_should_preserve_an_existing_plugins_active_state() {
         let tmp_dir = tempdir().unwrap();
@@ -1637,6 +2016,74 @@ mod tests {
         assert!(!load_order.is_active("Blank - Different.esp"));
     }
 
+    #[test]
+    fn validate_plugin_names_should_error_if_given_duplicate_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let filenames = vec!["Blank.esp", "blank.esp"];
+        match load_order.validate_plugin_names(&filenames).unwrap_err() {
+            Error::DuplicatePlugin(name) => assert_eq!("blank.esp", name),
+            e => panic!("Wrong error type: {:?}", e),
+        }
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn validate_plugin_names_should_error_if_given_an_invalid_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let filenames = vec!["Blank.esp", "missing.esp"];
+        assert!(load_order.validate_plugin_names(&filenames).is_err());
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn validate_plugin_names_should_error_if_given_a_list_with_plugins_before_masters() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let filenames = vec!["Blank.esp", "Blank.esm"];
+        match load_order.validate_plugin_names(&filenames).unwrap_err() {
+            Error::NonMasterBeforeMaster { master, non_master } => {
+                assert_eq!("Blank.esm", master);
+                assert_eq!("Blank.esp", non_master);
+            }
+            e => panic!("Wrong error type: {:?}", e),
+        }
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn validate_plugin_names_should_return_the_plugins_without_replacing_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let filenames = vec!["Morrowind.esm", "Blank.esm", "Blank - Different.esp"];
+        let plugins = load_order.validate_plugin_names(&filenames).unwrap();
+
+        let names: Vec<_> = plugins.iter().map(Plugin::name).collect();
+        assert_eq!(filenames, names);
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn replace_plugins_should_not_replace_the_plugins_if_the_order_is_unchanged() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        let plugins_ptr = load_order.plugins().as_ptr();
+        let filenames = vec!["morrowind.esm", "Blank.esp", "Blank - Different.esp"];
+        load_order.replace_plugins(&filenames).unwrap();
+
+        assert_eq!(plugins_ptr, load_order.plugins().as_ptr());
+    }
+
     #[test]
     fn replace_plugins_should_error_if_given_duplicate_plugins() {
         let tmp_dir = tempdir().unwrap();
@@ -1701,6 +2148,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn replace_plugins_should_error_if_the_game_master_does_not_load_first() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esm", &load_order.game_settings());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let filenames = vec![
+            "Blank.esm",
+            "Skyrim.esm",
+            "Blank.esp",
+            "Blank - Different.esp",
+        ];
+
+        match load_order.replace_plugins(&filenames).unwrap_err() {
+            Error::GameMasterMustLoadFirst(name) => assert_eq!("Skyrim.esm", name),
+            e => panic!("Wrong error type: {:?}", e),
+        }
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
     #[test]
     fn replace_plugins_should_not_error_if_an_early_loading_plugin_is_missing() {
         let tmp_dir = tempdir().unwrap();
@@ -2268,4 +2737,126 @@ mod tests {
         let first_non_master = super::find_first_non_master_position(&plugins);
         assert_eq!(1, first_non_master.unwrap());
     }
+
+    #[test]
+    fn plugin_listing_cache_should_skip_the_scan_if_the_plugins_directory_is_unchanged() {
+        let tmp_dir = tempdir().unwrap();
+        let directories = [tmp_dir.path().to_path_buf()];
+        let mut game_settings = game_settings_for_test(GameId::SkyrimSE, &tmp_dir.path());
+        game_settings.set_cache_plugin_listing(true);
+        let mut cache = PluginListingCache::default();
+
+        let scan_count = std::cell::Cell::new(0);
+        let scan = || {
+            scan_count.set(scan_count.get() + 1);
+            vec!["Blank.esp".to_string()]
+        };
+
+        assert_eq!(
+            vec!["Blank.esp"],
+            cache.listing(&game_settings, &directories, scan)
+        );
+        assert_eq!(
+            vec!["Blank.esp"],
+            cache.listing(&game_settings, &directories, scan)
+        );
+        assert_eq!(1, scan_count.get());
+    }
+
+    #[test]
+    fn plugin_listing_cache_should_rescan_if_a_file_is_added_to_or_removed_from_a_directory() {
+        let tmp_dir = tempdir().unwrap();
+        let directories = [tmp_dir.path().to_path_buf()];
+        let mut game_settings = game_settings_for_test(GameId::SkyrimSE, &tmp_dir.path());
+        game_settings.set_cache_plugin_listing(true);
+        let mut cache = PluginListingCache::default();
+
+        let scan_count = std::cell::Cell::new(0);
+        let scan = || {
+            scan_count.set(scan_count.get() + 1);
+            Vec::new()
+        };
+
+        cache.listing(&game_settings, &directories, scan);
+        assert_eq!(1, scan_count.get());
+
+        std::fs::write(tmp_dir.path().join("Blank.esp"), "").unwrap();
+        cache.listing(&game_settings, &directories, scan);
+        assert_eq!(2, scan_count.get());
+
+        std::fs::remove_file(tmp_dir.path().join("Blank.esp")).unwrap();
+        cache.listing(&game_settings, &directories, scan);
+        assert_eq!(3, scan_count.get());
+    }
+
+    #[test]
+    fn directory_state_should_compare_entry_names_if_the_directory_was_recently_modified() {
+        let tmp_dir = tempdir().unwrap();
+        let mut state = DirectoryState::new(tmp_dir.path());
+        assert!(state.entry_names.is_some());
+
+        // Simulate a filesystem that doesn't record the change in the
+        // directory's modification time.
+        std::fs::write(tmp_dir.path().join("Blank.esp"), "").unwrap();
+        state.modification_time = directory_modification_time(tmp_dir.path());
+
+        assert!(!state.is_unchanged());
+    }
+
+    #[test]
+    fn directory_state_should_not_list_the_directory_if_it_was_not_recently_modified() {
+        let tmp_dir = tempdir().unwrap();
+        let mut state = DirectoryState {
+            path: tmp_dir.path().to_path_buf(),
+            modification_time: directory_modification_time(tmp_dir.path()),
+            entry_names: None,
+        };
+        assert!(state.is_unchanged());
+
+        std::fs::write(tmp_dir.path().join("Blank.esp"), "").unwrap();
+        assert!(!state.is_unchanged());
+
+        // An unchanged modification time is trusted without listing the
+        // directory's entries.
+        state.modification_time = directory_modification_time(tmp_dir.path());
+        assert!(state.is_unchanged());
+    }
+
+    #[test]
+    fn plugin_listing_cache_should_rescan_if_it_is_invalidated() {
+        let tmp_dir = tempdir().unwrap();
+        let directories = [tmp_dir.path().to_path_buf()];
+        let mut game_settings = game_settings_for_test(GameId::SkyrimSE, &tmp_dir.path());
+        game_settings.set_cache_plugin_listing(true);
+        let mut cache = PluginListingCache::default();
+
+        let scan_count = std::cell::Cell::new(0);
+        let scan = || {
+            scan_count.set(scan_count.get() + 1);
+            Vec::new()
+        };
+
+        cache.listing(&game_settings, &directories, scan);
+        game_settings.invalidate_plugin_listing();
+        cache.listing(&game_settings, &directories, scan);
+        assert_eq!(2, scan_count.get());
+    }
+
+    #[test]
+    fn plugin_listing_cache_should_always_rescan_if_it_is_disabled() {
+        let tmp_dir = tempdir().unwrap();
+        let directories = [tmp_dir.path().to_path_buf()];
+        let game_settings = game_settings_for_test(GameId::SkyrimSE, &tmp_dir.path());
+        let mut cache = PluginListingCache::default();
+
+        let scan_count = std::cell::Cell::new(0);
+        let scan = || {
+            scan_count.set(scan_count.get() + 1);
+            Vec::new()
+        };
+
+        cache.listing(&game_settings, &directories, scan);
+        cache.listing(&game_settings, &directories, scan);
+        assert_eq!(2, scan_count.get());
+    }
 }
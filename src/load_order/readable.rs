@@ -16,8 +16,148 @@
  * You should have received a copy of the GNU General Public License
  * along with libloadorder. If not, see <http://www.gnu.org/licenses/>.
  */
+use std::time::SystemTime;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use super::mutable::{
+    find_plugins, game_master_must_load_first, insert_position, validate_no_circular_masters,
+    validate_plugins_load_before_their_masters,
+};
+#[cfg(feature = "serde")]
+use super::serializable::SerializableLoadOrder;
+use super::snapshot::ReadableSnapshot;
+use crate::enums::{ActivationState, Error, PluginType};
 use crate::game_settings::GameSettings;
-use crate::plugin::Plugin;
+use crate::ghostable_path::GhostablePath;
+use crate::plugin::{trim_dot_ghost, Plugin};
+
+pub const MAX_ACTIVE_FULL_PLUGINS: usize = 255;
+pub const MAX_ACTIVE_LIGHT_PLUGINS: usize = 4096;
+pub const MAX_ACTIVE_MEDIUM_PLUGINS: usize = 256;
+
+/// The numbers of active plugins of each type, which each have their own
+/// active plugins limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActivePluginCounts {
+    pub full: usize,
+    pub medium: usize,
+    pub light: usize,
+}
+
+impl From<PluginCounts> for ActivePluginCounts {
+    fn from(counts: PluginCounts) -> Self {
+        ActivePluginCounts {
+            full: counts.full,
+            medium: counts.medium,
+            light: counts.light,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PluginCounts {
+    pub light: usize,
+    pub medium: usize,
+    pub full: usize,
+}
+
+impl PluginCounts {
+    pub fn count_plugin(&mut self, plugin: &Plugin) {
+        if plugin.is_light_plugin() {
+            self.light += 1;
+        } else if plugin.is_medium_plugin() {
+            self.medium += 1;
+        } else {
+            self.full += 1;
+        }
+    }
+
+    pub fn max_active_full_plugins(&self) -> usize {
+        let modifier = if self.medium > 0 && self.light > 0 {
+            2
+        } else if self.medium > 0 || self.light > 0 {
+            1
+        } else {
+            0
+        };
+        MAX_ACTIVE_FULL_PLUGINS - modifier
+    }
+
+    pub fn exceeds_limits(&self) -> bool {
+        self.full > self.max_active_full_plugins()
+            || self.medium > MAX_ACTIVE_MEDIUM_PLUGINS
+            || self.light > MAX_ACTIVE_LIGHT_PLUGINS
+    }
+}
+
+pub fn count_active_plugins<T: ReadableLoadOrder + ?Sized>(load_order: &T) -> PluginCounts {
+    let mut counts = PluginCounts::default();
+
+    for plugin in load_order.loaded_plugins().iter().filter(|p| p.is_active()) {
+        counts.count_plugin(plugin);
+    }
+
+    counts
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PluginState {
+    pub index: usize,
+    pub is_active: bool,
+    pub is_master: bool,
+    pub is_light_master: bool,
+}
+
+/// Details of an active plugin, as reported by
+/// ReadableLoadOrderExt::active_plugins_detailed().
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActivePluginInfo {
+    pub name: String,
+    pub index: usize,
+    /// The plugin's mod index as it would appear in the upper bytes of its
+    /// FormIDs, e.g. "0A", "FE:001" for a light plugin or "FD:01" for a
+    /// medium plugin.
+    pub mod_index: String,
+    pub plugin_type: PluginType,
+}
+
+struct PluginTypeRuns<'a> {
+    plugins: &'a [Plugin],
+}
+
+impl<'a> Iterator for PluginTypeRuns<'a> {
+    type Item = (PluginType, &'a [Plugin]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let run_type = plugin_type(self.plugins.first()?);
+        let run_length = self
+            .plugins
+            .iter()
+            .position(|p| plugin_type(p) != run_type)
+            .unwrap_or(self.plugins.len());
+
+        let (run, rest) = self.plugins.split_at(run_length);
+        self.plugins = rest;
+
+        Some((run_type, run))
+    }
+}
+
+fn plugin_type(plugin: &Plugin) -> PluginType {
+    if plugin.is_light_plugin() {
+        PluginType::LightMaster
+    } else if plugin.is_medium_plugin() {
+        PluginType::MediumMaster
+    } else if plugin.is_update_plugin() {
+        PluginType::Override
+    } else if plugin.is_master_file() {
+        PluginType::Master
+    } else {
+        PluginType::Normal
+    }
+}
 
 pub trait ReadableLoadOrderBase {
     fn plugins(&self) -> &[Plugin];
@@ -25,6 +165,16 @@ pub trait ReadableLoadOrderBase {
     fn game_settings_base(&self) -> &GameSettings;
 }
 
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct PluginEntry<'a> {
+    name: &'a str,
+    index: usize,
+    active: bool,
+    master: bool,
+    light: bool,
+}
+
 pub trait ReadableLoadOrder {
     fn game_settings(&self) -> &GameSettings;
 
@@ -37,9 +187,345 @@ pub trait ReadableLoadOrder {
     fn active_plugin_names(&self) -> Vec<&str>;
 
     fn is_active(&self, plugin_name: &str) -> bool;
+
+    /// The plugins in the load order, in load order. The
+    /// `ReadableLoadOrderExt` queries are built on this, so implementations
+    /// outside libloadorder should override it: by default it gives no
+    /// plugins.
+    fn loaded_plugins(&self) -> &[Plugin] {
+        &[]
+    }
 }
 
-impl<T: ReadableLoadOrderBase> ReadableLoadOrder for T {
+/// Convenience queries built on a load order's plugins. These are provided
+/// for every `ReadableLoadOrder`, including `dyn WritableLoadOrder`, so they
+/// don't need to be implemented.
+pub trait ReadableLoadOrderExt: ReadableLoadOrder {
+    /// Get whether the given plugin is active, inactive or not in the load
+    /// order.
+    fn activation_state(&self, plugin_name: &str) -> ActivationState {
+        match self.index_of(plugin_name) {
+            Some(index) if self.loaded_plugins()[index].is_active() => ActivationState::Active,
+            Some(_) => ActivationState::Inactive,
+            None => ActivationState::NotPresent,
+        }
+    }
+
+    /// Check if the given plugin is implicitly active, so can't be
+    /// deactivated.
+    fn is_implicitly_active(&self, plugin_name: &str) -> bool {
+        self.game_settings().is_implicitly_active(plugin_name)
+    }
+
+    /// The names of implicitly active plugins that are in the load order, in
+    /// load order.
+    fn implicitly_active_plugin_names(&self) -> Vec<&str> {
+        self.loaded_plugins()
+            .iter()
+            .map(Plugin::name)
+            .filter(|n| self.game_settings().is_implicitly_active(n))
+            .collect()
+    }
+
+    /// Get the given plugin's load order index and flags, or `None` if it
+    /// isn't in the load order.
+    fn plugin_state(&self, plugin_name: &str) -> Option<PluginState> {
+        self.loaded_plugins()
+            .iter()
+            .enumerate()
+            .find(|(_, p)| p.name_matches(plugin_name))
+            .map(|(index, p)| PluginState {
+                index,
+                is_active: p.is_active(),
+                is_master: p.is_master_file(),
+                is_light_master: p.is_light_plugin(),
+            })
+    }
+
+    /// Classify the given plugin, or return `None` if it isn't in the load
+    /// order.
+    fn plugin_type(&self, plugin_name: &str) -> Option<PluginType> {
+        self.loaded_plugins()
+            .iter()
+            .find(|p| p.name_matches(plugin_name))
+            .map(plugin_type)
+    }
+
+    /// The modification time the given plugin had when it was loaded, or
+    /// `None` if it isn't in the load order.
+    fn plugin_modification_time(&self, plugin_name: &str) -> Option<SystemTime> {
+        self.loaded_plugins()
+            .iter()
+            .find(|p| p.name_matches(plugin_name))
+            .map(Plugin::modification_time)
+    }
+
+    /// Iterate over contiguous runs of plugins of the same type, in load
+    /// order. As masters load before non-masters, their runs come first.
+    fn grouped_by_type(&self) -> Box<dyn Iterator<Item = (PluginType, &[Plugin])> + '_> {
+        Box::new(PluginTypeRuns {
+            plugins: self.loaded_plugins(),
+        })
+    }
+
+    /// The names of master files that are not light plugins, in load order.
+    fn master_plugin_names(&self) -> Vec<String> {
+        self.loaded_plugins()
+            .iter()
+            .filter(|p| p.is_master_file() && !p.is_light_plugin())
+            .map(|p| p.name().to_string())
+            .collect()
+    }
+
+    /// The names of light plugins, in load order.
+    fn light_master_plugin_names(&self) -> Vec<String> {
+        self.loaded_plugins()
+            .iter()
+            .filter(|p| p.is_light_plugin())
+            .map(|p| p.name().to_string())
+            .collect()
+    }
+
+    /// The names of plugins that are neither master files nor light plugins,
+    /// in load order.
+    fn normal_plugin_names(&self) -> Vec<String> {
+        self.loaded_plugins()
+            .iter()
+            .filter(|p| !p.is_master_file() && !p.is_light_plugin())
+            .map(|p| p.name().to_string())
+            .collect()
+    }
+
+    /// Iterate over the plugins in load order, yielding each plugin's name,
+    /// whether it is active and whether it is a master.
+    fn iter_plugins(&self) -> Box<dyn Iterator<Item = (&str, bool, bool)> + '_> {
+        Box::new(
+            self.loaded_plugins()
+                .iter()
+                .map(|p| (p.name(), p.is_active(), p.is_master_file())),
+        )
+    }
+
+    /// The index that the game requires its master file to load at: `Some(0)`
+    /// for games that require it to load first, and `None` for games that
+    /// allow it to be moved.
+    fn game_master_index(&self) -> Option<usize> {
+        game_master_must_load_first(self.game_settings()).then_some(0)
+    }
+
+    /// Get the index that the given plugin would be given if it was added to
+    /// the load order, without adding it.
+    ///
+    /// Returns `Error::DuplicatePlugin` if the plugin is already in the load
+    /// order, and `Error::InvalidPath` if it isn't installed (or isn't a
+    /// plugin). Other errors from reading the plugin's header, such as
+    /// `Error::PluginParsingError`, are also returned.
+    fn would_insert_at(&self, plugin_name: &str) -> Result<usize, Error> {
+        if self.index_of(plugin_name).is_some() {
+            return Err(Error::DuplicatePlugin(plugin_name.to_string()));
+        }
+
+        let plugin = Plugin::new(plugin_name, self.game_settings())?;
+
+        Ok(insert_position(self, &plugin).unwrap_or(self.loaded_plugins().len()))
+    }
+
+    /// Check that no master file loads after a master file that depends on
+    /// it, which the game would resolve by hoisting it.
+    fn check_hoisting(&self) -> Result<(), Error> {
+        validate_plugins_load_before_their_masters(self.loaded_plugins())
+    }
+
+    /// Check that no plugin depends on itself through its masters. The game
+    /// can't load plugins that form such a cycle. Loading a load order runs
+    /// the same check, so this is only needed after its plugins change.
+    fn check_circular_masters(&self) -> Result<(), Error> {
+        validate_no_circular_masters(self.loaded_plugins())
+    }
+
+    /// The names of plugins in the load order that have masters which aren't
+    /// installed, paired with the names of those masters. The game won't
+    /// load these plugins. A plugin whose masters can't be read is paired
+    /// with the error instead, so that it doesn't stop the other plugins from
+    /// being checked.
+    fn plugins_with_missing_masters(&self) -> Vec<(String, Result<Vec<String>, Error>)> {
+        self.loaded_plugins()
+            .iter()
+            .filter_map(|plugin| {
+                let missing_masters = plugin.masters().map(|masters| {
+                    masters
+                        .into_iter()
+                        .filter(|m| self.game_settings().plugin_path(m).resolve_path().is_err())
+                        .collect::<Vec<_>>()
+                });
+
+                match missing_masters {
+                    Ok(masters) if masters.is_empty() => None,
+                    result => Some((plugin.name().to_string(), result)),
+                }
+            })
+            .collect()
+    }
+
+    /// Details of each active plugin, in load order.
+    fn active_plugins_detailed(&self) -> Vec<ActivePluginInfo> {
+        let mut counts = PluginCounts::default();
+
+        self.loaded_plugins()
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_active())
+            .map(|(index, plugin)| {
+                let mod_index = if plugin.is_light_plugin() {
+                    format!("FE:{:03X}", counts.light)
+                } else if plugin.is_medium_plugin() {
+                    format!("FD:{:02X}", counts.medium)
+                } else {
+                    format!("{:02X}", counts.full)
+                };
+                counts.count_plugin(plugin);
+
+                ActivePluginInfo {
+                    name: plugin.name().to_string(),
+                    index,
+                    mod_index,
+                    plugin_type: plugin_type(plugin),
+                }
+            })
+            .collect()
+    }
+
+    /// The filenames of all valid plugins that are installed for the game,
+    /// whether or not they're in the load order. Ghosted plugins' filenames
+    /// are given without their ".ghost" extension.
+    fn installed_plugin_names(&self) -> Vec<String> {
+        let game_settings = self.game_settings();
+        find_plugins(game_settings)
+            .into_iter()
+            .filter(|f| Plugin::new(f, game_settings).is_ok())
+            .map(|f| trim_dot_ghost(&f).to_string())
+            .collect()
+    }
+
+    /// Serialize the load order to a JSON array that gives each plugin's
+    /// name, index, active state and master and light flags.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Result<String, Error> {
+        let entries: Vec<_> = self
+            .loaded_plugins()
+            .iter()
+            .enumerate()
+            .map(|(index, plugin)| PluginEntry {
+                name: plugin.name(),
+                index,
+                active: plugin.is_active(),
+                master: plugin.is_master_file(),
+                light: plugin.is_light_plugin(),
+            })
+            .collect();
+
+        serde_json::to_string(&entries).map_err(|e| Error::SerializationError(e.to_string()))
+    }
+
+    /// Get a representation of the load order that can be serialized and
+    /// later applied to another installation of the same game.
+    #[cfg(feature = "serde")]
+    fn to_serializable(&self) -> SerializableLoadOrder {
+        SerializableLoadOrder::from_load_order(self)
+    }
+
+    /// Take an owned copy of the load order's plugin names and states that
+    /// can be read from other threads.
+    fn readable_snapshot(&self) -> ReadableSnapshot {
+        ReadableSnapshot::from_load_order(self)
+    }
+
+    /// The number of active plugins that are neither light nor medium plugins.
+    fn count_active_normal_plugins(&self) -> usize {
+        count_active_plugins(self).full
+    }
+
+    /// The number of active light plugins, which is always 0 for games that
+    /// don't support light plugins.
+    fn count_active_light_masters(&self) -> usize {
+        count_active_plugins(self).light
+    }
+
+    fn active_plugin_counts(&self) -> ActivePluginCounts {
+        count_active_plugins(self).into()
+    }
+
+    /// The names of the active plugins that would need to be deactivated to
+    /// bring each type of plugin back within its active plugins limit,
+    /// starting from the end of the load order. Implicitly active plugins
+    /// are never included.
+    fn excess_active_plugins(&self) -> Vec<String> {
+        let mut counts = count_active_plugins(self);
+        let max_active_full_plugins = counts.max_active_full_plugins();
+
+        let mut excess_plugins = Vec::new();
+        for plugin in self
+            .loaded_plugins()
+            .iter()
+            .rev()
+            .filter(|p| p.is_active() && !self.game_settings().is_implicitly_active(p.name()))
+        {
+            let (count, limit) = if plugin.is_light_plugin() {
+                (&mut counts.light, MAX_ACTIVE_LIGHT_PLUGINS)
+            } else if plugin.is_medium_plugin() {
+                (&mut counts.medium, MAX_ACTIVE_MEDIUM_PLUGINS)
+            } else {
+                (&mut counts.full, max_active_full_plugins)
+            };
+
+            if *count > limit {
+                *count -= 1;
+                excess_plugins.push(plugin.name().to_string());
+            }
+        }
+
+        excess_plugins
+    }
+
+    /// The mod index that the next full plugin to be activated would be
+    /// given, or `None` if no more full plugins can be activated.
+    fn next_free_normal_index(&self) -> Option<usize> {
+        let counts = count_active_plugins(self);
+
+        if counts.full < counts.max_active_full_plugins() {
+            Some(counts.full)
+        } else {
+            None
+        }
+    }
+
+    /// The index within the light plugin block (mod index 0xFE) that the next
+    /// light plugin to be activated would be given, or `None` if no more light
+    /// plugins can be activated.
+    fn next_free_light_index(&self) -> Option<usize> {
+        if !self.game_settings().id().supports_light_plugins() {
+            return None;
+        }
+
+        let counts = count_active_plugins(self);
+
+        // The first active light plugin claims mod index 0xFE, which must not
+        // already be occupied by a full plugin.
+        let counts_with_light = PluginCounts {
+            light: counts.light.max(1),
+            ..counts
+        };
+        if counts.light < MAX_ACTIVE_LIGHT_PLUGINS
+            && counts.full <= counts_with_light.max_active_full_plugins()
+        {
+            Some(counts.light)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: ReadableLoadOrderBase + ?Sized> ReadableLoadOrder for T {
     fn game_settings(&self) -> &GameSettings {
         self.game_settings_base()
     }
@@ -67,13 +553,16 @@ impl<T: ReadableLoadOrderBase> ReadableLoadOrder for T {
     }
 
     fn is_active(&self, plugin_name: &str) -> bool {
+        matches!(self.activation_state(plugin_name), ActivationState::Active)
+    }
+
+    fn loaded_plugins(&self) -> &[Plugin] {
         self.plugins()
-            .iter()
-            .find(|p| p.name_matches(plugin_name))
-            .map_or(false, |p| p.is_active())
     }
 }
 
+impl<T: ReadableLoadOrder + ?Sized> ReadableLoadOrderExt for T {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,6 +572,7 @@ mod tests {
     use tempfile::tempdir;
 
     use crate::enums::GameId;
+    use crate::load_order::mutable::find_first_non_master_position;
     use crate::load_order::tests::mock_game_files;
     use crate::tests::copy_to_test_dir;
 
@@ -128,6 +618,63 @@ mod tests {
         }
     }
 
+    fn add_active_plugins(
+        load_order: &mut TestLoadOrder,
+        source_plugin_name: &str,
+        plugin_count: usize,
+        suffix: &str,
+    ) {
+        for i in 0..plugin_count {
+            let name = format!("Blank{}.{}", i, suffix);
+            copy_to_test_dir(source_plugin_name, &name, &load_order.game_settings);
+            let mut plugin = Plugin::new(&name, &load_order.game_settings).unwrap();
+            plugin.activate().unwrap();
+            load_order.plugins.push(plugin);
+        }
+    }
+
+    fn prepare_starfield(game_dir: &Path) -> TestLoadOrder {
+        let (game_settings, plugins) = mock_game_files(GameId::Starfield, game_dir);
+        TestLoadOrder {
+            game_settings,
+            plugins,
+        }
+    }
+
+    #[test]
+    fn activation_state_should_be_active_for_an_active_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        assert_eq!(
+            ActivationState::Active,
+            load_order.activation_state("Blank.esp")
+        );
+    }
+
+    #[test]
+    fn activation_state_should_be_inactive_for_an_inactive_plugin_in_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        assert_eq!(
+            ActivationState::Inactive,
+            load_order.activation_state("Blank - Different.esp")
+        );
+    }
+
+    #[test]
+    fn activation_state_should_be_not_present_for_a_plugin_not_in_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        assert_eq!(
+            ActivationState::NotPresent,
+            load_order.activation_state("Blank - Master Dependent.esp")
+        );
+        assert!(!load_order.is_active("Blank - Master Dependent.esp"));
+    }
+
     #[test]
     fn plugin_names_should_return_filenames_for_plugins_in_load_order() {
         let tmp_dir = tempdir().unwrap();
@@ -175,6 +722,136 @@ mod tests {
         assert_eq!(1, load_order.index_of("blank.esp").unwrap());
     }
 
+    #[test]
+    fn plugin_state_should_match_index_of_and_is_active() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        for name in load_order.plugin_names() {
+            let state = load_order.plugin_state(name).unwrap();
+            assert_eq!(load_order.index_of(name).unwrap(), state.index);
+            assert_eq!(load_order.is_active(name), state.is_active);
+            assert!(!state.is_light_master);
+        }
+
+        let state = load_order.plugin_state("oblivion.esm").unwrap();
+        assert!(state.is_master);
+        assert!(load_order.plugin_state("Blank.esm").is_none());
+    }
+
+    #[test]
+    fn installed_plugin_names_should_include_plugins_that_are_not_in_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        let mut names = load_order.installed_plugin_names();
+        names.sort();
+
+        let mut expected = vec![
+            "Blank - Different.esp",
+            "Blank - Master Dependent.esp",
+            "Blank.esm",
+            "Blank.esp",
+            "Blàñk.esp",
+            load_order.game_settings().master_file(),
+        ];
+        expected.sort();
+
+        assert_eq!(expected, names);
+        assert!(load_order
+            .index_of("Blank - Master Dependent.esp")
+            .is_none());
+    }
+
+    #[test]
+    fn plugins_with_missing_masters_should_list_plugins_whose_masters_are_not_installed() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(&tmp_dir.path());
+
+        let plugin_name = "Blank - Master Dependent.esp";
+        load_order
+            .plugins
+            .push(Plugin::new(plugin_name, &load_order.game_settings).unwrap());
+
+        assert!(load_order.plugins_with_missing_masters().is_empty());
+
+        std::fs::remove_file(load_order.game_settings.plugin_path("Blank.esm")).unwrap();
+
+        let plugins_with_missing_masters = load_order.plugins_with_missing_masters();
+        assert_eq!(1, plugins_with_missing_masters.len());
+        assert_eq!(plugin_name, plugins_with_missing_masters[0].0);
+        assert_eq!(
+            vec!["Blank.esm"],
+            *plugins_with_missing_masters[0].1.as_ref().unwrap()
+        );
+    }
+
+    #[test]
+    fn active_plugins_detailed_should_line_up_with_individual_queries() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, plugins) = mock_game_files(GameId::SkyrimSE, &tmp_dir.path());
+        let mut load_order = TestLoadOrder {
+            game_settings,
+            plugins,
+        };
+        add_active_plugins(&mut load_order, "Blank.esp", 2, "esl");
+        add_active_plugins(&mut load_order, "Blank.esp", 1, "esp");
+
+        let details = load_order.active_plugins_detailed();
+
+        let names: Vec<_> = details.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(load_order.active_plugin_names(), names);
+
+        for detail in &details {
+            assert_eq!(load_order.index_of(&detail.name), Some(detail.index));
+            assert_eq!(
+                load_order.plugin_type(&detail.name),
+                Some(detail.plugin_type)
+            );
+        }
+
+        let mod_indexes: Vec<_> = details.iter().map(|d| d.mod_index.as_str()).collect();
+        assert_eq!(vec!["00", "FE:000", "FE:001", "01"], mod_indexes);
+    }
+
+    #[test]
+    fn would_insert_at_should_give_a_master_the_index_after_the_last_master() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        assert_eq!(1, load_order.would_insert_at("Blank.esm").unwrap());
+        assert!(load_order.index_of("Blank.esm").is_none());
+    }
+
+    #[test]
+    fn would_insert_at_should_give_a_non_master_the_index_after_the_last_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        assert_eq!(
+            load_order.plugins().len(),
+            load_order
+                .would_insert_at("Blank - Master Dependent.esp")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn would_insert_at_should_error_if_the_plugin_is_invalid_or_already_loaded() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        match load_order.would_insert_at("missing.esp").unwrap_err() {
+            Error::InvalidPath(path) => assert!(path.ends_with("missing.esp")),
+            e => panic!("Expected invalid path error, got {:?}", e),
+        }
+
+        match load_order.would_insert_at("Blank.esp").unwrap_err() {
+            Error::DuplicatePlugin(name) => assert_eq!("Blank.esp", name),
+            e => panic!("Expected duplicate plugin error, got {:?}", e),
+        }
+    }
+
     #[test]
     fn plugin_at_should_return_none_if_given_an_out_of_bounds_index() {
         let tmp_dir = tempdir().unwrap();
@@ -208,6 +885,23 @@ mod tests {
         assert_eq!(expected_plugin_names, load_order.active_plugin_names());
     }
 
+    #[test]
+    fn implicitly_active_plugin_names_should_list_implicitly_active_plugins_in_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, plugins) = mock_game_files(GameId::Skyrim, &tmp_dir.path());
+        let load_order = TestLoadOrder {
+            game_settings,
+            plugins,
+        };
+
+        assert!(load_order.is_implicitly_active("Skyrim.esm"));
+        assert!(!load_order.is_implicitly_active("Blank.esp"));
+        assert_eq!(
+            vec!["Skyrim.esm"],
+            load_order.implicitly_active_plugin_names()
+        );
+    }
+
     #[test]
     fn is_active_should_return_false_for_an_inactive_plugin() {
         let tmp_dir = tempdir().unwrap();
@@ -239,4 +933,290 @@ mod tests {
 
         assert!(load_order.is_active("blank.esp"));
     }
+
+    #[test]
+    fn count_active_plugins_methods_should_count_oblivion_plugins_as_normal() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(&tmp_dir.path());
+
+        add_active_plugins(&mut load_order, "Blank.esm", 2, "esm");
+
+        assert_eq!(3, load_order.count_active_normal_plugins());
+        assert_eq!(0, load_order.count_active_light_masters());
+    }
+
+    #[test]
+    fn count_active_plugins_methods_should_count_fallout4_light_plugins_separately() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, plugins) = mock_game_files(GameId::Fallout4, &tmp_dir.path());
+        let mut load_order = TestLoadOrder {
+            game_settings,
+            plugins,
+        };
+
+        add_active_plugins(&mut load_order, "Blank.esm", 2, "esm");
+        add_active_plugins(&mut load_order, "Blank.esm", 3, "esl");
+
+        assert_eq!(3, load_order.count_active_normal_plugins());
+        assert_eq!(3, load_order.count_active_light_masters());
+    }
+
+    #[test]
+    fn excess_active_plugins_should_be_empty_if_no_limits_are_exceeded() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_starfield(&tmp_dir.path());
+
+        add_active_plugins(&mut load_order, "Blank.full.esm", 254, "full.esm");
+
+        assert!(load_order.excess_active_plugins().is_empty());
+    }
+
+    #[test]
+    fn excess_active_plugins_should_list_plugins_over_the_limit_from_the_end_of_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_starfield(&tmp_dir.path());
+
+        add_active_plugins(&mut load_order, "Blank.full.esm", 260, "full.esm");
+
+        copy_to_test_dir(
+            "Blank.full.esm",
+            "Constellation.esm",
+            &load_order.game_settings,
+        );
+        let mut plugin = Plugin::new("Constellation.esm", &load_order.game_settings).unwrap();
+        plugin.activate().unwrap();
+        load_order.plugins.push(plugin);
+
+        let active_plugins = load_order.active_plugin_names().len();
+
+        let expected_plugins: Vec<_> = (253..260)
+            .rev()
+            .map(|i| format!("Blank{}.full.esm", i))
+            .collect();
+        assert_eq!(expected_plugins, load_order.excess_active_plugins());
+        assert_eq!(active_plugins, load_order.active_plugin_names().len());
+    }
+
+    #[test]
+    fn next_free_normal_index_should_return_the_number_of_active_full_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_starfield(&tmp_dir.path());
+
+        assert_eq!(Some(1), load_order.next_free_normal_index());
+
+        add_active_plugins(&mut load_order, "Blank.full.esm", 10, "full.esm");
+        add_active_plugins(&mut load_order, "Blank.small.esm", 10, "small.esm");
+
+        assert_eq!(Some(11), load_order.next_free_normal_index());
+    }
+
+    #[test]
+    fn next_free_normal_index_should_return_none_if_the_full_plugin_limit_has_been_reached() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_starfield(&tmp_dir.path());
+
+        add_active_plugins(&mut load_order, "Blank.full.esm", 253, "full.esm");
+        assert_eq!(Some(254), load_order.next_free_normal_index());
+
+        add_active_plugins(&mut load_order, "Blank.small.esm", 1, "small.esm");
+        assert!(load_order.next_free_normal_index().is_none());
+    }
+
+    #[test]
+    fn next_free_light_index_should_return_none_if_the_game_does_not_support_light_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        assert!(load_order.next_free_light_index().is_none());
+    }
+
+    #[test]
+    fn next_free_light_index_should_return_the_number_of_active_light_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_starfield(&tmp_dir.path());
+
+        assert_eq!(Some(0), load_order.next_free_light_index());
+
+        add_active_plugins(&mut load_order, "Blank.small.esm", 10, "small.esm");
+
+        assert_eq!(Some(10), load_order.next_free_light_index());
+    }
+
+    #[test]
+    fn next_free_light_index_should_return_none_if_the_light_plugin_limit_has_been_reached() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_starfield(&tmp_dir.path());
+
+        add_active_plugins(&mut load_order, "Blank.small.esm", 4096, "small.esm");
+
+        assert!(load_order.next_free_light_index().is_none());
+    }
+
+    #[test]
+    fn next_free_light_index_should_return_none_if_a_full_plugin_occupies_the_light_plugin_index() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare_starfield(&tmp_dir.path());
+
+        add_active_plugins(&mut load_order, "Blank.full.esm", 253, "full.esm");
+        assert_eq!(Some(0), load_order.next_free_light_index());
+
+        add_active_plugins(&mut load_order, "Blank.full.esm", 1, "extra.full.esm");
+        assert!(load_order.next_free_light_index().is_none());
+    }
+
+    #[test]
+    fn plugin_names_by_type_should_return_the_plugins_of_each_type_in_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, mut plugins) = mock_game_files(GameId::SkyrimSE, &tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esl", &game_settings);
+        plugins.insert(1, Plugin::new("Blank.esm", &game_settings).unwrap());
+        plugins.insert(2, Plugin::new("Blank.esl", &game_settings).unwrap());
+
+        let load_order = TestLoadOrder {
+            game_settings,
+            plugins,
+        };
+
+        assert_eq!(
+            vec!["Skyrim.esm", "Blank.esm"],
+            load_order.master_plugin_names()
+        );
+        assert_eq!(vec!["Blank.esl"], load_order.light_master_plugin_names());
+        assert_eq!(
+            vec!["Blank.esp", "Blank - Different.esp"],
+            load_order.normal_plugin_names()
+        );
+    }
+
+    #[test]
+    fn light_master_plugin_names_should_be_empty_if_the_game_does_not_support_light_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_with_ghosted_plugin(&tmp_dir.path());
+
+        assert_eq!(
+            vec!["Oblivion.esm", "Blank - Different.esm"],
+            load_order.master_plugin_names()
+        );
+        assert!(load_order.light_master_plugin_names().is_empty());
+    }
+
+    #[test]
+    fn plugin_type_should_classify_masters_and_normal_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        assert_eq!(
+            Some(PluginType::Master),
+            load_order.plugin_type("Oblivion.esm")
+        );
+        assert_eq!(
+            Some(PluginType::Normal),
+            load_order.plugin_type("blank.esp")
+        );
+        assert_eq!(None, load_order.plugin_type("missing.esp"));
+    }
+
+    #[test]
+    fn grouped_by_type_should_split_masters_from_non_masters() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(&tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esm", &load_order.game_settings);
+        let plugin = Plugin::new("Blank.esm", &load_order.game_settings).unwrap();
+        load_order.plugins.insert(1, plugin);
+
+        let groups: Vec<_> = load_order
+            .grouped_by_type()
+            .map(|(plugin_type, plugins)| {
+                let names: Vec<_> = plugins.iter().map(Plugin::name).collect();
+                (plugin_type, names)
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                (PluginType::Master, vec!["Oblivion.esm", "Blank.esm"]),
+                (
+                    PluginType::Normal,
+                    vec!["Blank.esp", "Blank - Different.esp"]
+                ),
+            ],
+            groups
+        );
+
+        let first_non_master_position =
+            find_first_non_master_position(load_order.plugins()).unwrap();
+        assert_eq!(first_non_master_position, groups[0].1.len());
+    }
+
+    #[test]
+    fn grouped_by_type_should_yield_nothing_for_an_empty_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(&tmp_dir.path());
+        load_order.plugins.clear();
+
+        assert_eq!(0, load_order.grouped_by_type().count());
+    }
+
+    #[test]
+    fn plugin_type_should_classify_light_masters_for_skyrim_se() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, mut plugins) = mock_game_files(GameId::SkyrimSE, &tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esl", &game_settings);
+        plugins.push(Plugin::new("Blank.esl", &game_settings).unwrap());
+
+        let load_order = TestLoadOrder {
+            game_settings,
+            plugins,
+        };
+
+        assert_eq!(
+            Some(PluginType::LightMaster),
+            load_order.plugin_type("Blank.esl")
+        );
+        assert_eq!(
+            Some(PluginType::Master),
+            load_order.plugin_type("Skyrim.esm")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn to_json_should_serialize_each_plugin_in_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(&tmp_dir.path());
+
+        let json: serde_json::Value = serde_json::from_str(&load_order.to_json().unwrap()).unwrap();
+
+        let expected = serde_json::json!([
+            { "name": "Oblivion.esm", "index": 0, "active": false, "master": true, "light": false },
+            { "name": "Blank.esp", "index": 1, "active": true, "master": false, "light": false },
+            {
+                "name": "Blank - Different.esp",
+                "index": 2,
+                "active": false,
+                "master": false,
+                "light": false
+            },
+        ]);
+        assert_eq!(expected, json);
+    }
+
+    #[test]
+    fn iter_plugins_should_yield_the_name_active_state_and_master_flag_of_each_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare_with_ghosted_plugin(&tmp_dir.path());
+
+        let plugins: Vec<_> = load_order.iter_plugins().collect();
+
+        let names: Vec<_> = plugins.iter().map(|(name, _, _)| *name).collect();
+        assert_eq!(load_order.plugin_names(), names);
+
+        for (name, is_active, is_master) in plugins {
+            assert_eq!(load_order.is_active(name), is_active);
+            assert_eq!(name.ends_with(".esm"), is_master);
+        }
+    }
 }
@@ -0,0 +1,171 @@
+/*
+ * This file is part of libloadorder
+ *
+ * Copyright (C) 2026 libloadorder contributors
+ *
+ * libloadorder is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * libloadorder is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with libloadorder. If not, see <http://www.gnu.org/licenses/>.
+ */
+use serde::{Deserialize, Serialize};
+
+use super::readable::ReadableLoadOrderExt;
+use super::writable::WritableLoadOrder;
+use crate::enums::{Error, GameId};
+use crate::game_settings::GameSettings;
+
+/// A representation of a load order that does not depend on the filesystem,
+/// so that it can be stored and later applied to another installation of the
+/// same game.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializableLoadOrder {
+    pub game: GameId,
+    /// Plugin filenames and their active states, in load order.
+    pub entries: Vec<(String, bool)>,
+}
+
+impl SerializableLoadOrder {
+    pub fn from_load_order<T: ReadableLoadOrderExt + ?Sized>(load_order: &T) -> Self {
+        SerializableLoadOrder {
+            game: load_order.game_settings().id(),
+            entries: load_order
+                .iter_plugins()
+                .map(|(name, is_active, _)| (name.to_string(), is_active))
+                .collect(),
+        }
+    }
+
+    /// Load the given game's current load order and then apply this load
+    /// order's plugin positions and active states to it. Installed plugins
+    /// that are not listed are kept, inactive, after the listed plugins, and
+    /// listed plugins that are not installed are skipped.
+    pub fn into_load_order(
+        self,
+        game_settings: GameSettings,
+    ) -> Result<Box<dyn WritableLoadOrder>, Error> {
+        if game_settings.id() != self.game {
+            return Err(Error::GameIdMismatch {
+                expected: self.game,
+                actual: game_settings.id(),
+            });
+        }
+
+        let mut load_order = game_settings.into_load_order();
+        load_order.load()?;
+
+        let unlisted_plugin_names: Vec<String> = load_order
+            .plugin_names()
+            .into_iter()
+            .filter(|n| {
+                !self
+                    .entries
+                    .iter()
+                    .any(|(e, _)| unicase::eq(e.as_str(), *n))
+            })
+            .map(str::to_string)
+            .collect();
+
+        let plugin_names: Vec<&str> = self
+            .entries
+            .iter()
+            .map(|(n, _)| n.as_str())
+            .chain(unlisted_plugin_names.iter().map(String::as_str))
+            .collect();
+        let skipped_plugin_names = load_order.import_from(&plugin_names)?;
+
+        let active_plugin_names: Vec<&str> = self
+            .entries
+            .iter()
+            .filter(|(n, is_active)| *is_active && !skipped_plugin_names.contains(n))
+            .map(|(n, _)| n.as_str())
+            .collect();
+        load_order.set_active_plugins(&active_plugin_names)?;
+
+        Ok(load_order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::tempdir;
+
+    use crate::load_order::tests::mock_game_files;
+
+    #[test]
+    fn serializable_load_order_should_round_trip_through_json() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, _) = mock_game_files(GameId::Oblivion, &tmp_dir.path());
+
+        let mut load_order = game_settings.clone().into_load_order();
+        load_order.load().unwrap();
+        let last_index = load_order.plugin_names().len() - 1;
+        load_order
+            .set_plugin_index("Blank.esp", last_index)
+            .unwrap();
+        load_order.activate("Blank - Different.esp").unwrap();
+
+        let json = serde_json::to_string(&load_order.to_serializable()).unwrap();
+        let serializable: SerializableLoadOrder = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(GameId::Oblivion, serializable.game);
+
+        let new_load_order = serializable.into_load_order(game_settings).unwrap();
+        assert_eq!(load_order.plugin_names(), new_load_order.plugin_names());
+        assert_eq!(
+            load_order.active_plugin_names(),
+            new_load_order.active_plugin_names()
+        );
+    }
+
+    #[test]
+    fn into_load_order_should_error_if_the_game_settings_are_for_a_different_game() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, _) = mock_game_files(GameId::Oblivion, &tmp_dir.path());
+
+        let serializable = SerializableLoadOrder {
+            game: GameId::Skyrim,
+            entries: Vec::new(),
+        };
+
+        match serializable.into_load_order(game_settings) {
+            Err(Error::GameIdMismatch { expected, actual }) => {
+                assert_eq!(GameId::Skyrim, expected);
+                assert_eq!(GameId::Oblivion, actual);
+            }
+            _ => panic!("Expected a game ID mismatch error"),
+        }
+    }
+
+    #[test]
+    fn into_load_order_should_skip_plugins_that_are_not_installed() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, _) = mock_game_files(GameId::Oblivion, &tmp_dir.path());
+
+        let serializable = SerializableLoadOrder {
+            game: GameId::Oblivion,
+            entries: vec![
+                ("Oblivion.esm".to_string(), true),
+                ("Blank.esm".to_string(), false),
+                ("missing.esp".to_string(), true),
+                ("Blank.esp".to_string(), true),
+            ],
+        };
+
+        let load_order = serializable.into_load_order(game_settings).unwrap();
+
+        assert!(load_order.index_of("missing.esp").is_none());
+        assert_eq!(Some(2), load_order.index_of("Blank.esp"));
+        assert!(load_order.is_active("Blank.esp"));
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * This file is part of libloadorder
+ *
+ * Copyright (C) 2026 libloadorder contributors
+ *
+ * libloadorder is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * libloadorder is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with libloadorder. If not, see <http://www.gnu.org/licenses/>.
+ */
+use std::sync::Arc;
+
+use unicase::eq;
+
+use super::readable::ReadableLoadOrderExt;
+use crate::enums::GameId;
+use crate::plugin::trim_dot_ghost;
+
+/// An owned copy of a load order's plugin names and states, which can be
+/// shared between threads and read while the load order it was taken from
+/// is modified. Cloning a snapshot is cheap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadableSnapshot {
+    game_id: GameId,
+    /// Plugin filenames, active states and master flags, in load order.
+    entries: Arc<Vec<(String, bool, bool)>>,
+}
+
+impl ReadableSnapshot {
+    pub fn from_load_order<T: ReadableLoadOrderExt + ?Sized>(load_order: &T) -> Self {
+        ReadableSnapshot {
+            game_id: load_order.game_settings().id(),
+            entries: Arc::new(
+                load_order
+                    .iter_plugins()
+                    .map(|(name, is_active, is_master)| (name.to_string(), is_active, is_master))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn game_id(&self) -> GameId {
+        self.game_id
+    }
+
+    pub fn plugin_names(&self) -> Vec<&str> {
+        self.entries.iter().map(|(n, _, _)| n.as_str()).collect()
+    }
+
+    pub fn active_plugin_names(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(_, is_active, _)| *is_active)
+            .map(|(n, _, _)| n.as_str())
+            .collect()
+    }
+
+    pub fn index_of(&self, plugin_name: &str) -> Option<usize> {
+        let plugin_name = trim_dot_ghost(plugin_name);
+        self.entries
+            .iter()
+            .position(|(n, _, _)| eq(n.as_str(), plugin_name))
+    }
+
+    pub fn is_active(&self, plugin_name: &str) -> bool {
+        self.index_of(plugin_name)
+            .is_some_and(|i| self.entries[i].1)
+    }
+
+    pub fn is_master(&self, plugin_name: &str) -> bool {
+        self.index_of(plugin_name)
+            .is_some_and(|i| self.entries[i].2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    use tempfile::tempdir;
+
+    use crate::load_order::tests::mock_game_files;
+
+    #[test]
+    fn snapshot_should_be_unchanged_by_later_changes_to_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let (game_settings, _) = mock_game_files(GameId::Oblivion, &tmp_dir.path());
+
+        let mut load_order = game_settings.into_load_order();
+        load_order.load().unwrap();
+
+        let snapshot = load_order.readable_snapshot();
+        let plugin_names = load_order
+            .plugin_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let active_plugin_names = load_order
+            .active_plugin_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        load_order.set_plugin_index("Blank.esp", 2).unwrap();
+        load_order.activate("Blank - Different.esp").unwrap();
+        load_order.deactivate("Blank.esp").unwrap();
+
+        let handle = thread::spawn(move || {
+            assert_eq!(GameId::Oblivion, snapshot.game_id());
+            assert_eq!(plugin_names, snapshot.plugin_names());
+            assert_eq!(active_plugin_names, snapshot.active_plugin_names());
+            assert_eq!(Some(1), snapshot.index_of("blank.esp"));
+            assert!(snapshot.is_active("Blank.esp"));
+            assert!(!snapshot.is_active("Blank - Different.esp"));
+            assert!(snapshot.is_master("Oblivion.esm"));
+        });
+        handle.join().unwrap();
+
+        assert_eq!(Some(2), load_order.index_of("Blank.esp"));
+        assert!(!load_order.is_active("Blank.esp"));
+    }
+}
@@ -110,6 +110,36 @@ pub fn set_timestamp_order(plugin_names: &[&str], parent_path: &Path) {
     }
 }
 
+/// Write a plugin that has only a header record listing the given masters.
+/// The header format is only valid for games other than Morrowind and
+/// Oblivion.
+pub fn write_plugin_with_masters(game_settings: &GameSettings, filename: &str, masters: &[&str]) {
+    let mut subrecords: Vec<u8> = Vec::new();
+    subrecords.extend(b"HEDR");
+    subrecords.extend(12u16.to_le_bytes());
+    subrecords.extend(1.7f32.to_le_bytes());
+    subrecords.extend(0u32.to_le_bytes());
+    subrecords.extend(0x800u32.to_le_bytes());
+
+    for master in masters {
+        let data = [master.as_bytes(), &[0]].concat();
+        subrecords.extend(b"MAST");
+        subrecords.extend(u16::try_from(data.len()).unwrap().to_le_bytes());
+        subrecords.extend(data);
+        subrecords.extend(b"DATA");
+        subrecords.extend(8u16.to_le_bytes());
+        subrecords.extend(0u64.to_le_bytes());
+    }
+
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.extend(b"TES4");
+    bytes.extend(u32::try_from(subrecords.len()).unwrap().to_le_bytes());
+    bytes.extend([0u8; 16]);
+    bytes.extend(subrecords);
+
+    std::fs::write(game_settings.plugins_directory().join(filename), bytes).unwrap();
+}
+
 pub fn mock_game_files(game_id: GameId, game_dir: &Path) -> (GameSettings, Vec<Plugin>) {
     let mut settings = game_settings_for_test(game_id, game_dir);
 
@@ -23,22 +23,31 @@ use std::path::{Path, PathBuf};
 
 use unicase::{eq, UniCase};
 
+use super::asterisk_based::{active_plugin_name_line_mapper, owning_plugin_line_mapper};
 use super::mutable::{
-    hoist_masters, load_active_plugins, plugin_line_mapper, read_plugin_names, MutableLoadOrder,
+    hoist_masters, load_active_plugins, plugin_line_mapper, read_plugin_names,
+    validate_no_circular_masters, MutableLoadOrder, PluginListingCache,
 };
-use super::readable::{ReadableLoadOrder, ReadableLoadOrderBase};
+use super::readable::{ActivePluginCounts, ReadableLoadOrder, ReadableLoadOrderBase};
 use super::strict_encode;
 use super::writable::{
-    activate, add, create_parent_dirs, deactivate, remove, set_active_plugins, WritableLoadOrder,
+    activate, activate_all_possible, active_plugins_missing_from_load_order_file, add,
+    apply_sorted_order, check_active_plugin_limits, clear, create_parent_dirs, deactivate,
+    deactivate_plugins, detect_active_plugins_file_method_mismatch, from_state,
+    handle_excess_active_plugins, import_from, remove, resolve_hoisting, save_as_method,
+    save_with_backup, set_active_plugins, set_active_plugins_ordered, set_active_plugins_reporting,
+    swap_plugins, to_state, would_exceed_limit, write_active_plugins, WritableLoadOrder,
 };
-use crate::enums::Error;
+use crate::enums::{Error, LoadOrderMethod};
 use crate::game_settings::GameSettings;
-use crate::plugin::{trim_dot_ghost, Plugin};
+use crate::plugin::{normalize_plugin_name, trim_dot_ghost, Plugin};
 
 #[derive(Clone, Debug)]
 pub struct TextfileBasedLoadOrder {
     game_settings: GameSettings,
     plugins: Vec<Plugin>,
+    active_plugins_file_method_mismatch: Option<LoadOrderMethod>,
+    plugin_listing_cache: PluginListingCache,
 }
 
 impl TextfileBasedLoadOrder {
@@ -46,6 +55,19 @@ impl TextfileBasedLoadOrder {
         Self {
             game_settings,
             plugins: Vec::new(),
+            active_plugins_file_method_mismatch: None,
+            plugin_listing_cache: PluginListingCache::default(),
+        }
+    }
+
+    /// Create a load order that holds the given plugins, in the given order,
+    /// without reading anything from the filesystem.
+    pub fn from_plugins(game_settings: GameSettings, plugins: Vec<Plugin>) -> Self {
+        Self {
+            game_settings,
+            plugins,
+            active_plugins_file_method_mismatch: None,
+            plugin_listing_cache: PluginListingCache::default(),
         }
     }
 
@@ -58,40 +80,12 @@ impl TextfileBasedLoadOrder {
     }
 
     fn read_from_active_plugins_file(&self) -> Result<Vec<(String, bool)>, Error> {
-        read_plugin_names(
-            self.game_settings().active_plugins_file(),
-            active_plugin_line_mapper,
-        )
-    }
-
-    fn save_load_order(&self) -> Result<(), Error> {
-        if let Some(file_path) = self.game_settings().load_order_file() {
-            create_parent_dirs(file_path)?;
-
-            let file = File::create(file_path).map_err(|e| Error::IoError(file_path.clone(), e))?;
-            let mut writer = BufWriter::new(file);
-            for plugin_name in self.plugin_names() {
-                writeln!(writer, "{}", plugin_name)
-                    .map_err(|e| Error::IoError(file_path.clone(), e))?;
-            }
-        }
-        Ok(())
-    }
-
-    fn save_active_plugins(&self) -> Result<(), Error> {
         let path = self.game_settings().active_plugins_file();
-        create_parent_dirs(path)?;
-
-        let file = File::create(path).map_err(|e| Error::IoError(path.clone(), e))?;
-        let mut writer = BufWriter::new(file);
-        for plugin_name in self.active_plugin_names() {
-            writer
-                .write_all(&strict_encode(plugin_name)?)
-                .map_err(|e| Error::IoError(path.clone(), e))?;
-            writeln!(writer).map_err(|e| Error::IoError(path.clone(), e))?;
+        if self.active_plugins_file_method_mismatch == Some(LoadOrderMethod::Asterisk) {
+            read_plugin_names(path, owning_plugin_line_mapper)
+        } else {
+            read_plugin_names(path, active_plugin_line_mapper)
         }
-
-        Ok(())
     }
 }
 
@@ -119,6 +113,9 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
     fn load(&mut self) -> Result<(), Error> {
         self.plugins_mut().clear();
 
+        self.active_plugins_file_method_mismatch =
+            detect_active_plugins_file_method_mismatch(self.game_settings())?;
+
         let load_order_file_exists = self
             .game_settings()
             .load_order_file()
@@ -131,23 +128,42 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
             self.read_from_active_plugins_file()?
         };
 
-        let filenames = self.find_plugins();
+        let filenames = self.plugin_listing_cache.find_plugins(&self.game_settings);
         self.load_unique_plugins(plugin_tuples, filenames);
 
         if load_order_file_exists {
-            load_active_plugins(self, plugin_line_mapper)?;
+            if self.active_plugins_file_method_mismatch == Some(LoadOrderMethod::Asterisk) {
+                load_active_plugins(self, active_plugin_name_line_mapper)?;
+            } else {
+                load_active_plugins(self, plugin_line_mapper)?;
+            }
         }
 
         self.add_implicitly_active_plugins()?;
 
+        validate_no_circular_masters(&self.plugins)?;
+
         hoist_masters(&mut self.plugins)?;
 
-        Ok(())
+        check_active_plugin_limits(self)
     }
 
     fn save(&mut self) -> Result<(), Error> {
-        self.save_load_order()?;
-        self.save_active_plugins()
+        handle_excess_active_plugins(self)?;
+
+        save_using_textfiles(self)
+    }
+
+    fn save_with_backup(&mut self) -> Result<(), Error> {
+        save_with_backup(self)
+    }
+
+    fn save_as_method(&mut self, method: LoadOrderMethod) -> Result<(), Error> {
+        save_as_method(self, method)
+    }
+
+    fn serialize_active_plugins(&self) -> Result<Vec<u8>, Error> {
+        serialize_active_plugins(self)
     }
 
     fn add(&mut self, plugin_name: &str) -> Result<usize, Error> {
@@ -162,10 +178,34 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
         self.replace_plugins(plugin_names)
     }
 
+    fn import_from(&mut self, plugin_names: &[&str]) -> Result<Vec<String>, Error> {
+        import_from(self, plugin_names)
+    }
+
+    fn validate_load_order(&self, plugin_names: &[&str]) -> Result<(), Error> {
+        self.validate_plugin_names(plugin_names).map(|_| ())
+    }
+
+    fn apply_sorted_order(&mut self, sorted_plugin_names: &[&str]) -> Result<(), Error> {
+        apply_sorted_order(self, sorted_plugin_names)
+    }
+
     fn set_plugin_index(&mut self, plugin_name: &str, position: usize) -> Result<usize, Error> {
         MutableLoadOrder::set_plugin_index(self, plugin_name, position)
     }
 
+    fn clear(&mut self) -> Result<(), Error> {
+        clear(self)
+    }
+
+    fn resolve_hoisting(&mut self) -> Result<(), Error> {
+        resolve_hoisting(self)
+    }
+
+    fn swap_plugins(&mut self, plugin_name_a: &str, plugin_name_b: &str) -> Result<(), Error> {
+        swap_plugins(self, plugin_name_a, plugin_name_b)
+    }
+
     fn is_self_consistent(&self) -> Result<bool, Error> {
         match check_self_consistency(self.game_settings())? {
             SelfConsistency::Inconsistent => Ok(false),
@@ -206,6 +246,14 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
         Ok(!all_plugins_listed)
     }
 
+    fn active_plugins_file_method_mismatch(&self) -> Option<LoadOrderMethod> {
+        self.active_plugins_file_method_mismatch
+    }
+
+    fn active_plugins_missing_from_load_order_file(&self) -> Result<Vec<String>, Error> {
+        active_plugins_missing_from_load_order_file(self)
+    }
+
     fn activate(&mut self, plugin_name: &str) -> Result<(), Error> {
         activate(self, plugin_name)
     }
@@ -214,9 +262,81 @@ impl WritableLoadOrder for TextfileBasedLoadOrder {
         deactivate(self, plugin_name)
     }
 
+    fn deactivate_plugins(&mut self, plugin_names: &[&str]) -> Result<(), Error> {
+        deactivate_plugins(self, plugin_names)
+    }
+
+    fn activate_all_possible(&mut self) -> Result<Vec<String>, Error> {
+        activate_all_possible(self)
+    }
+
+    fn would_exceed_limit(&self, plugin_name: &str) -> Result<bool, Error> {
+        would_exceed_limit(self, plugin_name)
+    }
+
     fn set_active_plugins(&mut self, active_plugin_names: &[&str]) -> Result<(), Error> {
         set_active_plugins(self, active_plugin_names)
     }
+
+    fn set_active_plugins_ordered(&mut self, active_plugin_names: &[&str]) -> Result<(), Error> {
+        set_active_plugins_ordered(self, active_plugin_names)
+    }
+
+    fn set_active_plugins_reporting(
+        &mut self,
+        active_plugin_names: &[&str],
+    ) -> Result<ActivePluginCounts, Error> {
+        set_active_plugins_reporting(self, active_plugin_names)
+    }
+
+    fn to_state(&self) -> Vec<(String, bool)> {
+        to_state(self)
+    }
+
+    fn from_state(&mut self, state: &[(String, bool)]) -> Result<(), Error> {
+        from_state(self, state)
+    }
+}
+
+pub fn save_using_textfiles<T: ReadableLoadOrderBase>(load_order: &T) -> Result<(), Error> {
+    if let Some(file_path) = load_order.game_settings().load_order_file() {
+        save_load_order(load_order, file_path)?;
+    }
+    save_active_plugins(load_order)
+}
+
+pub fn save_load_order<T: ReadableLoadOrderBase>(
+    load_order: &T,
+    file_path: &Path,
+) -> Result<(), Error> {
+    create_parent_dirs(file_path)?;
+
+    let file = File::create(file_path).map_err(|e| Error::IoError(file_path.to_path_buf(), e))?;
+    let mut writer = BufWriter::new(file);
+    for plugin_name in load_order.plugin_names() {
+        writeln!(writer, "{}", plugin_name)
+            .map_err(|e| Error::IoError(file_path.to_path_buf(), e))?;
+    }
+    Ok(())
+}
+
+pub fn save_active_plugins<T: ReadableLoadOrderBase>(load_order: &T) -> Result<(), Error> {
+    let content = serialize_active_plugins(load_order)?;
+
+    write_active_plugins(load_order.game_settings().active_plugins_file(), &content)
+}
+
+pub fn serialize_active_plugins<T: ReadableLoadOrderBase>(
+    load_order: &T,
+) -> Result<Vec<u8>, Error> {
+    let mut content = Vec::new();
+
+    for plugin_name in load_order.active_plugin_names() {
+        content.extend_from_slice(&strict_encode(plugin_name)?);
+        content.push(b'\n');
+    }
+
+    Ok(content)
 }
 
 pub fn read_utf8_plugin_names<F, T>(file_path: &Path, line_mapper: F) -> Result<Vec<T>, Error>
@@ -233,7 +353,11 @@ where
     file.read_to_string(&mut content)
         .map_err(|e| Error::IoError(file_path.to_path_buf(), e))?;
 
-    Ok(content.lines().filter_map(line_mapper).collect())
+    Ok(content
+        .lines()
+        .map(normalize_plugin_name)
+        .filter_map(line_mapper)
+        .collect())
 }
 
 enum SelfConsistency {
@@ -299,8 +423,9 @@ fn plugin_names_match(name1: &str, name2: &str) -> bool {
 mod tests {
     use super::*;
 
-    use crate::enums::GameId;
+    use crate::enums::{ExcessActivePluginsAction, GameId};
     use crate::load_order::tests::*;
+    use crate::load_order::AsteriskBasedLoadOrder;
     use crate::tests::copy_to_test_dir;
     use std::fs::{remove_dir_all, File};
     use std::io::Write;
@@ -312,6 +437,8 @@ mod tests {
         TextfileBasedLoadOrder {
             game_settings,
             plugins,
+            active_plugins_file_method_mismatch: None,
+            plugin_listing_cache: PluginListingCache::default(),
         }
     }
 
@@ -365,6 +492,31 @@ mod tests {
         assert!(load_order.index_of("Blank - Different.esp").is_none());
     }
 
+    #[test]
+    fn load_should_skip_a_corrupt_plugin_listed_in_the_active_plugins_file() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        let plugin_path = load_order
+            .game_settings()
+            .plugins_directory()
+            .join("Blank - Different.esp");
+        write_file(&plugin_path);
+
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &["Blank.esm", "Blank - Different.esp", "Blank.esp"],
+        );
+
+        load_order.load().unwrap();
+
+        assert!(load_order.index_of("Blank - Different.esp").is_none());
+        assert_eq!(
+            vec!["Skyrim.esm", "Blank.esm", "Blank.esp"],
+            load_order.active_plugin_names()
+        );
+    }
+
     #[test]
     fn load_should_get_load_order_from_load_order_file() {
         let tmp_dir = tempdir().unwrap();
@@ -388,6 +540,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_should_strip_a_bom_and_whitespace_from_load_order_file_lines() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        std::fs::write(
+            load_order.game_settings().load_order_file().unwrap(),
+            "\u{feff}Skyrim.esm\nBlank.esm  \n Blank - Different.esp\t\nBlank.esp\n",
+        )
+        .unwrap();
+
+        load_order.load().unwrap();
+
+        assert_eq!(
+            vec![
+                "Skyrim.esm",
+                "Blank.esm",
+                "Blank - Different.esp",
+                "Blank.esp"
+            ],
+            &load_order.plugin_names()[..4]
+        );
+    }
+
+    #[test]
+    fn load_should_strip_a_bom_from_the_active_plugins_file() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        std::fs::write(
+            load_order.game_settings().active_plugins_file(),
+            "\u{feff}Blank - Different.esp \r\nBlank.esm\r\n",
+        )
+        .unwrap();
+
+        load_order.load().unwrap();
+
+        assert!(load_order.is_active("Blank - Different.esp"));
+        assert!(load_order.is_active("Blank.esm"));
+    }
+
     #[test]
     fn load_should_hoist_masters_that_masters_depend_on_to_load_before_their_dependents() {
         let tmp_dir = tempdir().unwrap();
@@ -678,6 +871,79 @@ mod tests {
             .exists());
     }
 
+    fn count_backups(path: &Path) -> usize {
+        let prefix = format!("{}.", path.file_name().unwrap().to_string_lossy());
+        std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy().into_owned();
+                name.starts_with(&prefix) && name.ends_with(".bak")
+            })
+            .count()
+    }
+
+    #[test]
+    fn save_with_backup_should_back_up_existing_files_before_overwriting_them() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        let active_plugins_file = load_order.game_settings().active_plugins_file().clone();
+        let load_order_file = load_order
+            .game_settings()
+            .load_order_file()
+            .unwrap()
+            .clone();
+        if active_plugins_file.exists() {
+            std::fs::remove_file(&active_plugins_file).unwrap();
+        }
+        if load_order_file.exists() {
+            std::fs::remove_file(&load_order_file).unwrap();
+        }
+
+        load_order.save_with_backup().unwrap();
+
+        assert_eq!(0, count_backups(&active_plugins_file));
+        assert_eq!(0, count_backups(&load_order_file));
+
+        load_order.save_with_backup().unwrap();
+
+        assert_eq!(1, count_backups(&active_plugins_file));
+        assert_eq!(1, count_backups(&load_order_file));
+    }
+
+    #[test]
+    fn save_with_backup_should_only_keep_the_configured_number_of_backups() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        load_order.game_settings_mut().set_max_backups(2);
+        for _ in 0..5 {
+            load_order.save_with_backup().unwrap();
+        }
+
+        let active_plugins_file = load_order.game_settings().active_plugins_file();
+        let load_order_file = load_order.game_settings().load_order_file().unwrap();
+        assert_eq!(2, count_backups(active_plugins_file));
+        assert_eq!(2, count_backups(load_order_file));
+    }
+
+    #[test]
+    fn save_with_backup_should_not_back_up_files_if_max_backups_is_zero() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        load_order.game_settings_mut().set_max_backups(0);
+        load_order.save().unwrap();
+        load_order.save_with_backup().unwrap();
+
+        let active_plugins_file = load_order.game_settings().active_plugins_file();
+        let load_order_file = load_order.game_settings().load_order_file().unwrap();
+        assert!(active_plugins_file.exists());
+        assert_eq!(0, count_backups(active_plugins_file));
+        assert_eq!(0, count_backups(load_order_file));
+    }
+
     #[test]
     fn save_should_write_active_plugins_file() {
         let tmp_dir = tempdir().unwrap();
@@ -713,6 +979,128 @@ mod tests {
         };
     }
 
+    #[test]
+    fn save_as_method_should_write_an_asterisk_based_load_order_that_skyrim_se_can_read() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        load_order.plugins.swap(1, 2);
+        load_order.activate("Blank - Different.esp").unwrap();
+        load_order.deactivate("Blank.esp").unwrap();
+
+        load_order
+            .save_as_method(LoadOrderMethod::Asterisk)
+            .unwrap();
+
+        let mut asterisk_load_order =
+            AsteriskBasedLoadOrder::new(game_settings_for_test(GameId::SkyrimSE, &tmp_dir.path()));
+        asterisk_load_order.load().unwrap();
+
+        // Installed plugins that weren't in the saved load order get added
+        // when loading, so ignore them.
+        let read_plugin_names: Vec<&str> = asterisk_load_order
+            .plugin_names()
+            .into_iter()
+            .filter(|n| load_order.index_of(n).is_some())
+            .collect();
+        assert_eq!(load_order.plugin_names(), read_plugin_names);
+        assert!(asterisk_load_order.is_active("Blank - Different.esp"));
+        assert!(!asterisk_load_order.is_active("Blank.esp"));
+    }
+
+    #[test]
+    fn save_as_method_should_apply_the_excess_active_plugins_action() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        load_order
+            .game_settings
+            .set_excess_active_plugins_on_save(ExcessActivePluginsAction::Reject);
+        for i in 0..260 {
+            let name = format!("{}.esp", i);
+            copy_to_test_dir("Blank.esp", &name, load_order.game_settings());
+            let mut plugin = Plugin::new(&name, load_order.game_settings()).unwrap();
+            plugin.activate().unwrap();
+            load_order.plugins.push(plugin);
+        }
+
+        match load_order
+            .save_as_method(LoadOrderMethod::Timestamp)
+            .unwrap_err()
+        {
+            Error::TooManyActivePlugins { .. } => {}
+            e => panic!("Expected too many active plugins error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn active_plugins_missing_from_load_order_file_should_list_unlisted_active_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        load_order.activate("Blank - Different.esp").unwrap();
+        write_load_order_file(
+            load_order.game_settings(),
+            &["Skyrim.esm", "blank.esp", "Blank - Master Dependent.esp"],
+        );
+
+        assert_eq!(
+            vec!["Blank - Different.esp"],
+            load_order
+                .active_plugins_missing_from_load_order_file()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn load_should_not_report_a_method_mismatch_for_a_textfile_based_active_plugins_file() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        write_active_plugins_file(load_order.game_settings(), &["Blank.esp"]);
+        load_order.load().unwrap();
+
+        assert_eq!(None, load_order.active_plugins_file_method_mismatch());
+    }
+
+    #[test]
+    fn load_should_report_and_read_an_asterisk_based_active_plugins_file() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &["*Blank.esp", "Blank - Different.esp"],
+        );
+        load_order.load().unwrap();
+
+        assert_eq!(
+            Some(LoadOrderMethod::Asterisk),
+            load_order.active_plugins_file_method_mismatch()
+        );
+        assert!(load_order.is_active("Blank.esp"));
+        assert!(!load_order.is_active("Blank - Different.esp"));
+    }
+
+    #[test]
+    fn load_should_read_active_plugins_from_an_asterisk_based_file_if_a_load_order_file_exists() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        write_load_order_file(
+            load_order.game_settings(),
+            &["Skyrim.esm", "Blank.esp", "Blank - Different.esp"],
+        );
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &["*Blank.esp", "Blank - Different.esp"],
+        );
+        load_order.load().unwrap();
+
+        assert!(load_order.is_active("Blank.esp"));
+        assert!(!load_order.is_active("Blank - Different.esp"));
+    }
+
     #[test]
     fn is_self_consistent_should_return_true_when_no_load_order_file_exists() {
         let tmp_dir = tempdir().unwrap();
@@ -18,19 +18,28 @@
  */
 use std::cmp::Ordering;
 use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use rayon::prelude::*;
 use regex::Regex;
 
-use super::mutable::{hoist_masters, load_active_plugins, MutableLoadOrder};
-use super::readable::{ReadableLoadOrder, ReadableLoadOrderBase};
+use super::asterisk_based::active_plugin_name_line_mapper;
+use super::mutable::{
+    hoist_masters, load_active_plugins, read_plugin_names, validate_no_circular_masters,
+    MutableLoadOrder, PluginListingCache,
+};
+use super::readable::{ActivePluginCounts, ReadableLoadOrder, ReadableLoadOrderBase};
 use super::strict_encode;
 use super::writable::{
-    activate, add, create_parent_dirs, deactivate, remove, set_active_plugins, WritableLoadOrder,
+    activate, activate_all_possible, active_plugins_missing_from_load_order_file, add,
+    apply_sorted_order, check_active_plugin_limits, clear, deactivate, deactivate_plugins,
+    detect_active_plugins_file_method_mismatch, from_state, handle_excess_active_plugins,
+    import_from, remove, resolve_hoisting, save_as_method, save_with_backup, set_active_plugins,
+    set_active_plugins_ordered, set_active_plugins_reporting, swap_plugins, to_state,
+    would_exceed_limit, write_active_plugins, WritableLoadOrder,
 };
-use crate::enums::{Error, GameId};
+use crate::enums::{Error, GameId, LoadOrderMethod};
 use crate::game_settings::GameSettings;
 use crate::plugin::Plugin;
 
@@ -40,6 +49,8 @@ const GAME_FILES_HEADER: &[u8] = b"[Game Files]";
 pub struct TimestampBasedLoadOrder {
     game_settings: GameSettings,
     plugins: Vec<Plugin>,
+    active_plugins_file_method_mismatch: Option<LoadOrderMethod>,
+    plugin_listing_cache: PluginListingCache,
 }
 
 impl TimestampBasedLoadOrder {
@@ -47,42 +58,53 @@ impl TimestampBasedLoadOrder {
         Self {
             game_settings,
             plugins: Vec::new(),
+            active_plugins_file_method_mismatch: None,
+            plugin_listing_cache: PluginListingCache::default(),
         }
     }
 
-    fn load_plugins_from_dir(&self) -> Vec<Plugin> {
-        let filenames = self.find_plugins();
-        let game_settings = self.game_settings();
-
-        filenames
-            .par_iter()
-            .filter_map(|f| Plugin::new(f, game_settings).ok())
-            .collect()
+    /// Create a load order, checking that the game uses the timestamp-based
+    /// load order method.
+    pub fn try_new(game_settings: GameSettings) -> Result<Self, Error> {
+        if game_settings.load_order_method() == LoadOrderMethod::Timestamp {
+            Ok(Self::new(game_settings))
+        } else {
+            Err(Error::IncompatibleLoadOrderMethod {
+                game_id: game_settings.id(),
+                method: LoadOrderMethod::Timestamp,
+            })
+        }
     }
 
-    fn save_active_plugins(&mut self) -> Result<(), Error> {
-        let path = self.game_settings().active_plugins_file();
-        create_parent_dirs(path)?;
+    /// Create a load order that holds the given plugins, in the given order,
+    /// without reading anything from the filesystem.
+    pub fn from_plugins(game_settings: GameSettings, plugins: Vec<Plugin>) -> Self {
+        Self {
+            game_settings,
+            plugins,
+            active_plugins_file_method_mismatch: None,
+            plugin_listing_cache: PluginListingCache::default(),
+        }
+    }
 
-        let prelude = get_file_prelude(self.game_settings())?;
+    /// Get the timestamp that saving the load order would give each plugin,
+    /// in load order.
+    pub fn planned_timestamps(&self) -> Vec<(String, SystemTime)> {
+        self.plugins
+            .iter()
+            .map(|p| p.name().to_string())
+            .zip(padded_unique_timestamps(&self.plugins))
+            .collect()
+    }
 
-        let file = File::create(path).map_err(|e| Error::IoError(path.clone(), e))?;
-        let mut writer = BufWriter::new(file);
-        writer
-            .write_all(&prelude)
-            .map_err(|e| Error::IoError(path.clone(), e))?;
-        for (index, plugin_name) in self.active_plugin_names().iter().enumerate() {
-            if self.game_settings().id() == GameId::Morrowind {
-                write!(writer, "GameFile{}=", index)
-                    .map_err(|e| Error::IoError(path.clone(), e))?;
-            }
-            writer
-                .write_all(&strict_encode(plugin_name)?)
-                .map_err(|e| Error::IoError(path.clone(), e))?;
-            writeln!(writer).map_err(|e| Error::IoError(path.clone(), e))?;
-        }
+    fn load_plugins_from_dir(&mut self) -> Vec<Plugin> {
+        let filenames = self.plugin_listing_cache.find_plugins(&self.game_settings);
+        let game_settings = &self.game_settings;
 
-        Ok(())
+        filenames
+            .par_iter()
+            .filter_map(|f| Plugin::new(f, game_settings).ok())
+            .collect()
     }
 }
 
@@ -113,24 +135,51 @@ impl WritableLoadOrder for TimestampBasedLoadOrder {
         self.plugins = self.load_plugins_from_dir();
         self.plugins.par_sort_by(plugin_sorter);
 
-        let regex = Regex::new(r"(?i)GameFile[0-9]{1,3}=(.+\.es(?:m|p))")
+        let regex = Regex::new(r"(?i)GameFile([0-9]{1,3})=(.+\.es(?:m|p))")
             .expect("Hardcoded GameFile ini entry regex should be valid");
         let game_id = self.game_settings().id();
         let line_mapper = |line: &str| plugin_line_mapper(line, &regex, game_id);
 
-        load_active_plugins(self, line_mapper)?;
+        self.active_plugins_file_method_mismatch =
+            detect_active_plugins_file_method_mismatch(self.game_settings())?;
+        if self.active_plugins_file_method_mismatch == Some(LoadOrderMethod::Asterisk) {
+            load_active_plugins(self, active_plugin_name_line_mapper)?;
+        } else {
+            load_active_plugins(self, line_mapper)?;
+        }
+
+        if game_id == GameId::Morrowind {
+            let game_files = read_plugin_names(self.game_settings().active_plugins_file(), |l| {
+                game_file_line_mapper(l, &regex)
+            })?;
+            apply_game_file_order(&mut self.plugins, &game_files);
+        }
 
         self.add_implicitly_active_plugins()?;
 
+        validate_no_circular_masters(&self.plugins)?;
+
         hoist_masters(&mut self.plugins)?;
 
-        Ok(())
+        check_active_plugin_limits(self)
     }
 
     fn save(&mut self) -> Result<(), Error> {
-        save_load_order_using_timestamps(self)?;
+        handle_excess_active_plugins(self)?;
 
-        self.save_active_plugins()
+        save_using_timestamps(self)
+    }
+
+    fn save_with_backup(&mut self) -> Result<(), Error> {
+        save_with_backup(self)
+    }
+
+    fn save_as_method(&mut self, method: LoadOrderMethod) -> Result<(), Error> {
+        save_as_method(self, method)
+    }
+
+    fn serialize_active_plugins(&self) -> Result<Vec<u8>, Error> {
+        serialize_active_plugins(self)
     }
 
     fn add(&mut self, plugin_name: &str) -> Result<usize, Error> {
@@ -145,10 +194,34 @@ impl WritableLoadOrder for TimestampBasedLoadOrder {
         self.replace_plugins(plugin_names)
     }
 
+    fn import_from(&mut self, plugin_names: &[&str]) -> Result<Vec<String>, Error> {
+        import_from(self, plugin_names)
+    }
+
+    fn validate_load_order(&self, plugin_names: &[&str]) -> Result<(), Error> {
+        self.validate_plugin_names(plugin_names).map(|_| ())
+    }
+
+    fn apply_sorted_order(&mut self, sorted_plugin_names: &[&str]) -> Result<(), Error> {
+        apply_sorted_order(self, sorted_plugin_names)
+    }
+
     fn set_plugin_index(&mut self, plugin_name: &str, position: usize) -> Result<usize, Error> {
         MutableLoadOrder::set_plugin_index(self, plugin_name, position)
     }
 
+    fn clear(&mut self) -> Result<(), Error> {
+        clear(self)
+    }
+
+    fn resolve_hoisting(&mut self) -> Result<(), Error> {
+        resolve_hoisting(self)
+    }
+
+    fn swap_plugins(&mut self, plugin_name_a: &str, plugin_name_b: &str) -> Result<(), Error> {
+        swap_plugins(self, plugin_name_a, plugin_name_b)
+    }
+
     fn is_self_consistent(&self) -> Result<bool, Error> {
         Ok(true)
     }
@@ -159,6 +232,14 @@ impl WritableLoadOrder for TimestampBasedLoadOrder {
         Ok(false)
     }
 
+    fn active_plugins_file_method_mismatch(&self) -> Option<LoadOrderMethod> {
+        self.active_plugins_file_method_mismatch
+    }
+
+    fn active_plugins_missing_from_load_order_file(&self) -> Result<Vec<String>, Error> {
+        active_plugins_missing_from_load_order_file(self)
+    }
+
     fn activate(&mut self, plugin_name: &str) -> Result<(), Error> {
         activate(self, plugin_name)
     }
@@ -167,9 +248,46 @@ impl WritableLoadOrder for TimestampBasedLoadOrder {
         deactivate(self, plugin_name)
     }
 
+    fn deactivate_plugins(&mut self, plugin_names: &[&str]) -> Result<(), Error> {
+        deactivate_plugins(self, plugin_names)
+    }
+
+    fn activate_all_possible(&mut self) -> Result<Vec<String>, Error> {
+        activate_all_possible(self)
+    }
+
+    fn would_exceed_limit(&self, plugin_name: &str) -> Result<bool, Error> {
+        would_exceed_limit(self, plugin_name)
+    }
+
     fn set_active_plugins(&mut self, active_plugin_names: &[&str]) -> Result<(), Error> {
         set_active_plugins(self, active_plugin_names)
     }
+
+    fn set_active_plugins_ordered(&mut self, active_plugin_names: &[&str]) -> Result<(), Error> {
+        set_active_plugins_ordered(self, active_plugin_names)
+    }
+
+    fn set_active_plugins_reporting(
+        &mut self,
+        active_plugin_names: &[&str],
+    ) -> Result<ActivePluginCounts, Error> {
+        set_active_plugins_reporting(self, active_plugin_names)
+    }
+
+    fn to_state(&self) -> Vec<(String, bool)> {
+        to_state(self)
+    }
+
+    fn from_state(&mut self, state: &[(String, bool)]) -> Result<(), Error> {
+        from_state(self, state)
+    }
+}
+
+pub fn save_using_timestamps<T: MutableLoadOrder>(load_order: &mut T) -> Result<(), Error> {
+    save_load_order_using_timestamps(load_order)?;
+
+    save_active_plugins(load_order)
 }
 
 pub fn save_load_order_using_timestamps<T: MutableLoadOrder>(
@@ -177,6 +295,10 @@ pub fn save_load_order_using_timestamps<T: MutableLoadOrder>(
 ) -> Result<(), Error> {
     let timestamps = padded_unique_timestamps(load_order.plugins());
 
+    if has_timestamps(load_order.plugins(), &timestamps) {
+        return Ok(());
+    }
+
     load_order
         .plugins_mut()
         .par_iter_mut()
@@ -186,6 +308,28 @@ pub fn save_load_order_using_timestamps<T: MutableLoadOrder>(
         .map(|_| ())
 }
 
+fn save_active_plugins<T: ReadableLoadOrderBase>(load_order: &T) -> Result<(), Error> {
+    let content = serialize_active_plugins(load_order)?;
+
+    write_active_plugins(load_order.game_settings().active_plugins_file(), &content)
+}
+
+pub fn serialize_active_plugins<T: ReadableLoadOrderBase>(
+    load_order: &T,
+) -> Result<Vec<u8>, Error> {
+    let mut content = get_file_prelude(load_order.game_settings())?;
+
+    for (index, plugin_name) in load_order.active_plugin_names().iter().enumerate() {
+        if load_order.game_settings().id() == GameId::Morrowind {
+            content.extend_from_slice(format!("GameFile{}=", index).as_bytes());
+        }
+        content.extend_from_slice(&strict_encode(plugin_name)?);
+        content.push(b'\n');
+    }
+
+    Ok(content)
+}
+
 fn plugin_sorter(a: &Plugin, b: &Plugin) -> Ordering {
     if a.is_master_file() == b.is_master_file() {
         match a.modification_time().cmp(&b.modification_time()) {
@@ -199,12 +343,50 @@ fn plugin_sorter(a: &Plugin, b: &Plugin) -> Ordering {
     }
 }
 
+/// Get the index and plugin name from a Morrowind.ini GameFileN entry.
+fn game_file_line_mapper(line: &str, regex: &Regex) -> Option<(usize, String)> {
+    let captures = regex.captures(line)?;
+    let index = captures.get(1)?.as_str().parse().ok()?;
+    let name = captures.get(2)?.as_str().trim();
+
+    Some((index, name.to_owned()))
+}
+
+/// Morrowind.ini numbers its GameFile entries, so use those numbers to order
+/// the listed plugins relative to each other. Unlisted plugins keep their
+/// timestamp-based positions, and masters still load before non-masters.
+fn apply_game_file_order(plugins: &mut [Plugin], game_files: &[(usize, String)]) {
+    let game_file_index = |plugin: &Plugin| {
+        game_files
+            .iter()
+            .find(|(_, name)| plugin.name_matches(name))
+            .map(|(index, _)| *index)
+    };
+
+    for is_master in [true, false] {
+        let positions: Vec<usize> = plugins
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_master_file() == is_master && game_file_index(p).is_some())
+            .map(|(i, _)| i)
+            .collect();
+
+        let mut listed_plugins: Vec<Plugin> =
+            positions.iter().map(|i| plugins[*i].clone()).collect();
+        listed_plugins.sort_by_key(|p| game_file_index(p));
+
+        for (position, plugin) in positions.into_iter().zip(listed_plugins) {
+            plugins[position] = plugin;
+        }
+    }
+}
+
 fn plugin_line_mapper(mut line: &str, regex: &Regex, game_id: GameId) -> Option<String> {
     if game_id == GameId::Morrowind {
         line = regex
             .captures(line)
-            .and_then(|c| c.get(1))
-            .map_or("", |m| m.as_str());
+            .and_then(|c| c.get(2))
+            .map_or("", |m| m.as_str().trim());
     }
 
     if line.is_empty() || line.starts_with('#') {
@@ -214,6 +396,21 @@ fn plugin_line_mapper(mut line: &str, regex: &Regex, game_id: GameId) -> Option<
     }
 }
 
+/// Check if the plugins already have the given timestamps, so that saving the
+/// load order wouldn't change them. The files are checked in case they have
+/// been changed since the load order was loaded.
+fn has_timestamps(plugins: &[Plugin], timestamps: &[SystemTime]) -> bool {
+    plugins
+        .par_iter()
+        .zip(timestamps.par_iter())
+        .all(|(plugin, timestamp)| {
+            plugin.modification_time() == *timestamp
+                && plugin
+                    .modification_time_on_disk()
+                    .is_ok_and(|t| t == *timestamp)
+        })
+}
+
 fn padded_unique_timestamps(plugins: &[Plugin]) -> Vec<SystemTime> {
     let mut timestamps: Vec<SystemTime> = plugins.iter().map(Plugin::modification_time).collect();
 
@@ -256,6 +453,7 @@ mod tests {
     use super::*;
 
     use crate::enums::GameId;
+    use crate::load_order::readable::ReadableLoadOrderExt;
     use crate::load_order::tests::*;
     use crate::tests::copy_to_test_dir;
     use std::convert::TryInto;
@@ -269,6 +467,99 @@ mod tests {
         TimestampBasedLoadOrder {
             game_settings,
             plugins,
+            active_plugins_file_method_mismatch: None,
+            plugin_listing_cache: PluginListingCache::default(),
+        }
+    }
+
+    #[test]
+    fn active_plugins_missing_from_load_order_file_should_be_empty() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        assert!(load_order
+            .active_plugins_missing_from_load_order_file()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn planned_timestamps_should_match_the_timestamps_written_by_save() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        load_order.plugins.swap(1, 2);
+        let planned_timestamps = load_order.planned_timestamps();
+
+        load_order.save().unwrap();
+
+        let plugins_dir = load_order.game_settings().plugins_directory();
+        for (name, timestamp) in planned_timestamps {
+            let modified = plugins_dir.join(name).metadata().unwrap().modified();
+            assert_eq!(timestamp, modified.unwrap());
+        }
+    }
+
+    #[test]
+    fn add_should_insert_the_game_master_file_before_other_master_files() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        load_order.plugins.remove(0);
+        load_and_insert(&mut load_order, "Blank.esm");
+
+        assert_eq!(0, load_order.add("Oblivion.esm").unwrap());
+        assert_eq!(Some(0), load_order.index_of("Oblivion.esm"));
+    }
+
+    #[test]
+    fn load_should_report_and_read_an_asterisk_based_active_plugins_file() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &["*Blank.esp", "Blank - Different.esp"],
+        );
+        load_order.load().unwrap();
+
+        assert_eq!(
+            Some(LoadOrderMethod::Asterisk),
+            load_order.active_plugins_file_method_mismatch()
+        );
+        assert!(load_order.is_active("Blank.esp"));
+        assert!(!load_order.is_active("Blank - Different.esp"));
+    }
+
+    #[test]
+    fn game_master_index_should_be_none_for_timestamp_based_games() {
+        for game_id in [GameId::Morrowind, GameId::Oblivion] {
+            let tmp_dir = tempdir().unwrap();
+            let load_order = prepare(game_id, &tmp_dir.path());
+
+            assert!(load_order.game_master_index().is_none());
+        }
+    }
+
+    #[test]
+    fn try_new_should_succeed_for_a_timestamp_based_game() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = game_settings_for_test(GameId::Oblivion, &tmp_dir.path());
+
+        assert!(TimestampBasedLoadOrder::try_new(settings).is_ok());
+    }
+
+    #[test]
+    fn try_new_should_error_for_a_game_that_is_not_timestamp_based() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = game_settings_for_test(GameId::SkyrimSE, &tmp_dir.path());
+
+        match TimestampBasedLoadOrder::try_new(settings).unwrap_err() {
+            Error::IncompatibleLoadOrderMethod { game_id, method } => {
+                assert_eq!(GameId::SkyrimSE, game_id);
+                assert_eq!(LoadOrderMethod::Timestamp, method);
+            }
+            e => panic!("Expected incompatible load order method error, got {:?}", e),
         }
     }
 
@@ -390,6 +681,26 @@ mod tests {
         assert_eq!(expected_filenames, load_order.plugin_names());
     }
 
+    #[test]
+    fn plugin_modification_time_should_return_the_timestamp_the_plugin_was_loaded_with() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let plugin_path = load_order
+            .game_settings()
+            .plugins_directory()
+            .join("Blank - Different.esp");
+        set_file_timestamps(&plugin_path, 1321010051);
+
+        load_order.load().unwrap();
+
+        assert_eq!(
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1321010051)),
+            load_order.plugin_modification_time("Blank - Different.esp")
+        );
+        assert!(load_order.plugin_modification_time("missing.esp").is_none());
+    }
+
     #[test]
     fn load_should_empty_the_load_order_if_the_plugins_directory_does_not_exist() {
         let tmp_dir = tempdir().unwrap();
@@ -494,6 +805,60 @@ mod tests {
         assert_eq!(expected_filenames, load_order.active_plugin_names());
     }
 
+    #[test]
+    fn load_should_order_active_plugins_by_game_file_index_for_morrowind() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        let content = "isrealmorrowindini=false\n[Game Files]\nGameFile2=Blank.esp\nGameFile0=Blàñk.esp\nGameFile1=Blank - Different.esp\n";
+        std::fs::write(
+            load_order.game_settings().active_plugins_file(),
+            strict_encode(content).unwrap(),
+        )
+        .unwrap();
+
+        load_order.load().unwrap();
+
+        assert_eq!(
+            vec!["Blàñk.esp", "Blank - Different.esp", "Blank.esp"],
+            load_order.active_plugin_names()
+        );
+    }
+
+    #[test]
+    fn load_should_recognise_uppercase_plugin_file_extensions_for_morrowind() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        copy_to_test_dir("Blank.esp", "Blank - Upper.ESP", load_order.game_settings());
+        copy_to_test_dir("Blank.esm", "Blank - Mixed.Esm", load_order.game_settings());
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &["Blank - Mixed.Esm", "Blank - Upper.ESP"],
+        );
+
+        load_order.load().unwrap();
+
+        assert!(load_order.is_active("Blank - Upper.ESP"));
+        assert!(load_order.is_active("Blank - Mixed.Esm"));
+    }
+
+    #[test]
+    fn load_should_trim_whitespace_and_carriage_returns_in_active_plugins_file_for_morrowind() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        write_active_plugins_file(
+            load_order.game_settings(),
+            &[" Blàñk.esp\r", "Blank.esm \r\r"],
+        );
+
+        load_order.load().unwrap();
+        let expected_filenames = vec!["Blank.esm", "Blàñk.esp"];
+
+        assert_eq!(expected_filenames, load_order.active_plugin_names());
+    }
+
     #[test]
     fn save_should_preserve_the_existing_set_of_timestamps() {
         let tmp_dir = tempdir().unwrap();
@@ -529,6 +894,40 @@ mod tests {
         assert_eq!(old_timestamps, timestamps);
     }
 
+    #[test]
+    fn save_should_not_change_timestamps_if_the_load_order_is_unchanged() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let plugins_dir = load_order.game_settings().plugins_directory();
+        set_timestamps(
+            &plugins_dir,
+            &[
+                load_order.game_settings().master_file(),
+                "Blank.esm",
+                "Blank.esp",
+                "Blank - Different.esp",
+                "Blank - Master Dependent.esp",
+                "Blàñk.esp",
+            ],
+        );
+
+        load_order.load().unwrap();
+
+        let read_timestamps = |names: Vec<&str>| -> Vec<SystemTime> {
+            names
+                .iter()
+                .map(|n| plugins_dir.join(n).metadata().unwrap().modified().unwrap())
+                .collect()
+        };
+
+        let old_timestamps = read_timestamps(load_order.plugin_names());
+
+        load_order.save().unwrap();
+
+        assert_eq!(old_timestamps, read_timestamps(load_order.plugin_names()));
+    }
+
     #[test]
     fn save_should_deduplicate_plugin_timestamps() {
         let tmp_dir = tempdir().unwrap();
@@ -630,6 +1029,35 @@ mod tests {
         assert!(content.contains("isrealmorrowindini=false\n[Game Files]\n"));
     }
 
+    #[test]
+    fn serialize_active_plugins_should_match_the_file_written_by_save_for_oblivion() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let content = load_order.serialize_active_plugins().unwrap();
+
+        load_order.save().unwrap();
+
+        let saved = std::fs::read(load_order.game_settings().active_plugins_file()).unwrap();
+        assert_eq!(saved, content);
+    }
+
+    #[test]
+    fn serialize_active_plugins_should_match_the_file_written_by_save_for_morrowind() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Morrowind, &tmp_dir.path());
+
+        write_active_plugins_file(load_order.game_settings(), &["Blank.esp"]);
+
+        let content = load_order.serialize_active_plugins().unwrap();
+
+        load_order.save().unwrap();
+
+        let saved = std::fs::read(load_order.game_settings().active_plugins_file()).unwrap();
+        assert_eq!(saved, content);
+        assert!(content.starts_with(b"isrealmorrowindini=false\n[Game Files]\n"));
+    }
+
     #[test]
     fn save_should_error_if_an_active_plugin_filename_cannot_be_encoded_in_windows_1252() {
         let tmp_dir = tempdir().unwrap();
@@ -651,6 +1079,44 @@ mod tests {
         };
     }
 
+    #[test]
+    fn set_load_order_should_report_the_second_occurrence_of_a_duplicated_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        match load_order
+            .set_load_order(&["Oblivion.esm", "Blank.esp", "blank.esp"])
+            .unwrap_err()
+        {
+            Error::DuplicatePlugin(name) => assert_eq!("blank.esp", name),
+            e => panic!("Expected duplicate plugin error, got {:?}", e),
+        }
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn save_as_method_should_write_a_load_order_that_the_given_method_can_read() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        load_order
+            .save_as_method(LoadOrderMethod::Timestamp)
+            .unwrap();
+
+        let mut timestamp_load_order =
+            TimestampBasedLoadOrder::new(load_order.game_settings().clone());
+        timestamp_load_order.load().unwrap();
+        assert_eq!(
+            load_order.plugin_names(),
+            timestamp_load_order.plugin_names()
+        );
+        assert_eq!(
+            load_order.active_plugin_names(),
+            timestamp_load_order.active_plugin_names()
+        );
+    }
+
     #[test]
     fn is_self_consistent_should_return_true() {
         let tmp_dir = tempdir().unwrap();
@@ -17,21 +17,28 @@
  * along with libloadorder. If not, see <http://www.gnu.org/licenses/>.
  */
 use std::collections::HashSet;
-use std::fs::create_dir_all;
+use std::fs::{copy, create_dir_all, read_dir, remove_file, File};
+use std::io::{self, Write};
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use unicase::{eq, UniCase};
 
-use super::mutable::MutableLoadOrder;
-use super::readable::{ReadableLoadOrder, ReadableLoadOrderBase};
-use crate::enums::Error;
-use crate::plugin::Plugin;
+use super::asterisk_based::save_using_asterisks;
+use super::mutable::{
+    hoist_masters, plugin_line_mapper, read_plugin_names, validate_game_master_position,
+    validate_load_order, MutableLoadOrder,
+};
+use super::readable::{
+    count_active_plugins, ActivePluginCounts, PluginCounts, ReadableLoadOrder,
+    ReadableLoadOrderBase, ReadableLoadOrderExt,
+};
+use super::textfile_based::{read_utf8_plugin_names, save_active_plugins, save_load_order};
+use super::timestamp_based::save_using_timestamps;
+use crate::enums::{Error, ExcessActivePluginsAction, GameId, LoadOrderMethod};
+use crate::plugin::{trim_dot_ghost, Plugin};
 use crate::GameSettings;
 
-const MAX_ACTIVE_FULL_PLUGINS: usize = 255;
-const MAX_ACTIVE_LIGHT_PLUGINS: usize = 4096;
-const MAX_ACTIVE_MEDIUM_PLUGINS: usize = 256;
-
 pub trait WritableLoadOrder: ReadableLoadOrder {
     fn game_settings_mut(&mut self) -> &mut GameSettings;
 
@@ -39,23 +46,112 @@ pub trait WritableLoadOrder: ReadableLoadOrder {
 
     fn save(&mut self) -> Result<(), Error>;
 
+    /// Save the load order, first backing up the existing active plugins
+    /// and load order files. Only the newest backups are kept, up to the
+    /// number set in the game settings.
+    fn save_with_backup(&mut self) -> Result<(), Error>;
+
+    /// Save the load order using the given method's file formats instead of
+    /// the game's own method, e.g. to migrate a load order between Skyrim and
+    /// Skyrim Special Edition. Errors with IncompatibleLoadOrderMethod if the
+    /// game's load order can't be migrated to the given method.
+    fn save_as_method(&mut self, method: LoadOrderMethod) -> Result<(), Error>;
+
+    /// Get the content that save() would write to the active plugins file,
+    /// without writing it.
+    fn serialize_active_plugins(&self) -> Result<Vec<u8>, Error>;
+
     fn add(&mut self, plugin_name: &str) -> Result<usize, Error>;
 
     fn remove(&mut self, plugin_name: &str) -> Result<(), Error>;
 
     fn set_load_order(&mut self, plugin_names: &[&str]) -> Result<(), Error>;
 
+    /// Set the load order from a list of plugin names taken from elsewhere,
+    /// e.g. another game's load order. Plugins that aren't installed are
+    /// skipped, and their names are returned. Implicitly active plugins are
+    /// then added.
+    fn import_from(&mut self, plugin_names: &[&str]) -> Result<Vec<String>, Error>;
+
+    /// Check that the given load order would be accepted by `set_load_order()`
+    /// without changing the current load order.
+    fn validate_load_order(&self, plugin_names: &[&str]) -> Result<(), Error>;
+
+    /// Reorder the plugins already in the load order to match the given
+    /// sorted list (e.g. output from LOOT), keeping their active states.
+    /// Plugins that are not in the list keep their relative order after the
+    /// listed plugins.
+    fn apply_sorted_order(&mut self, sorted_plugin_names: &[&str]) -> Result<(), Error>;
+
     fn set_plugin_index(&mut self, plugin_name: &str, position: usize) -> Result<usize, Error>;
 
+    /// Remove all plugins from the load order, apart from the implicitly
+    /// active plugins that are installed, which are active.
+    fn clear(&mut self) -> Result<(), Error>;
+
+    /// Move any plugins that master files depend on to load before them, as
+    /// the game would hoist them.
+    fn resolve_hoisting(&mut self) -> Result<(), Error>;
+
+    /// Swap the positions of the two given plugins in the load order.
+    fn swap_plugins(&mut self, plugin_name_a: &str, plugin_name_b: &str) -> Result<(), Error>;
+
     fn is_self_consistent(&self) -> Result<bool, Error>;
 
     fn is_ambiguous(&self) -> Result<bool, Error>;
 
+    /// The load order method that the active plugins file appeared to have
+    /// been written using when the load order was last loaded, if it
+    /// differs from the game's method, e.g. because the file was copied from
+    /// another game. A textfile- or timestamp-based game's file that contains
+    /// asterisks is read as asterisk-based. Only asterisks can be detected,
+    /// so this is always `None` for asterisk-based games.
+    fn active_plugins_file_method_mismatch(&self) -> Option<LoadOrderMethod>;
+
+    /// Get the names of active plugins that aren't listed in the load order
+    /// file. Games without a load order file have no such plugins.
+    fn active_plugins_missing_from_load_order_file(&self) -> Result<Vec<String>, Error>;
+
     fn activate(&mut self, plugin_name: &str) -> Result<(), Error>;
 
     fn deactivate(&mut self, plugin_name: &str) -> Result<(), Error>;
 
+    /// Deactivate all the given plugins. If any of them are implicitly active
+    /// or not in the load order, an error is returned and no plugins are
+    /// deactivated.
+    fn deactivate_plugins(&mut self, plugin_names: &[&str]) -> Result<(), Error>;
+
+    /// Activate as many plugins as will fit within the active plugins limits,
+    /// in load order. The names of the plugins that could not be activated
+    /// are returned.
+    fn activate_all_possible(&mut self) -> Result<Vec<String>, Error>;
+
+    /// Check whether activating the given plugin would fail because it would
+    /// take the load order over an active plugins limit.
+    fn would_exceed_limit(&self, plugin_name: &str) -> Result<bool, Error>;
+
     fn set_active_plugins(&mut self, active_plugin_names: &[&str]) -> Result<(), Error>;
+
+    /// Set the active plugins as set_active_plugins() does, and also move
+    /// them into the given order relative to each other. Inactive plugins
+    /// keep their positions.
+    fn set_active_plugins_ordered(&mut self, active_plugin_names: &[&str]) -> Result<(), Error>;
+
+    /// Set the active plugins, then return the resulting numbers of active
+    /// plugins of each type.
+    fn set_active_plugins_reporting(
+        &mut self,
+        active_plugin_names: &[&str],
+    ) -> Result<ActivePluginCounts, Error>;
+
+    /// Get the plugin names and active states in load order.
+    fn to_state(&self) -> Vec<(String, bool)>;
+
+    /// Set the load order and active plugins from plugin names and active
+    /// states given in load order. If either is invalid, the load order is
+    /// left unchanged.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_state(&mut self, state: &[(String, bool)]) -> Result<(), Error>;
 }
 
 pub fn add<T: MutableLoadOrder>(load_order: &mut T, plugin_name: &str) -> Result<usize, Error> {
@@ -137,55 +233,104 @@ pub fn remove<T: MutableLoadOrder>(load_order: &mut T, plugin_name: &str) -> Res
     }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
-struct PluginCounts {
-    light: usize,
-    medium: usize,
-    full: usize,
-}
-
-impl PluginCounts {
-    fn count_plugin(&mut self, plugin: &Plugin) {
-        if plugin.is_light_plugin() {
-            self.light += 1;
-        } else if plugin.is_medium_plugin() {
-            self.medium += 1;
-        } else {
-            self.full += 1;
-        }
+/// If the game settings enforce a strict active plugins limit, check that
+/// there are no active plugins in excess of it.
+pub fn check_active_plugin_limits<T: ReadableLoadOrderBase>(load_order: &T) -> Result<(), Error> {
+    if !load_order.game_settings().strict_active_limit()
+        || load_order.excess_active_plugins().is_empty()
+    {
+        return Ok(());
     }
 
-    fn max_active_full_plugins(&self) -> usize {
-        let modifier = if self.medium > 0 && self.light > 0 {
-            2
-        } else if self.medium > 0 || self.light > 0 {
-            1
-        } else {
-            0
-        };
-        MAX_ACTIVE_FULL_PLUGINS - modifier
-    }
+    let counts = count_active_plugins(load_order);
+    Err(Error::TooManyActivePlugins {
+        light_count: counts.light,
+        medium_count: counts.medium,
+        full_count: counts.full,
+    })
 }
 
-fn count_active_plugins<T: ReadableLoadOrderBase>(load_order: &T) -> PluginCounts {
+fn count_plugins(existing_plugins: &[Plugin], existing_plugin_indexes: &[usize]) -> PluginCounts {
     let mut counts = PluginCounts::default();
 
-    for plugin in load_order.plugins().iter().filter(|p| p.is_active()) {
+    for index in existing_plugin_indexes {
+        let plugin = &existing_plugins[*index];
         counts.count_plugin(plugin);
     }
 
     counts
 }
 
-fn count_plugins(existing_plugins: &[Plugin], existing_plugin_indexes: &[usize]) -> PluginCounts {
-    let mut counts = PluginCounts::default();
-
-    for index in existing_plugin_indexes {
-        let plugin = &existing_plugins[*index];
-        counts.count_plugin(plugin);
+pub fn apply_sorted_order<T: MutableLoadOrder>(
+    load_order: &mut T,
+    sorted_plugin_names: &[&str],
+) -> Result<(), Error> {
+    if let Some(n) = sorted_plugin_names
+        .iter()
+        .find(|n| load_order.index_of(n).is_none())
+    {
+        return Err(Error::PluginNotFound(n.to_string()));
     }
 
-    counts
+    let unsorted_plugin_names: Vec<String> = load_order
+        .plugin_names()
+        .into_iter()
+        .filter(|n| !sorted_plugin_names.iter().any(|s| eq(*s, *n)))
+        .map(str::to_string)
+        .collect();
+
+    let mut plugin_names = sorted_plugin_names.to_vec();
+    plugin_names.extend(unsorted_plugin_names.iter().map(String::as_str));
+
+    load_order.replace_plugins(&plugin_names)
+}
+
+pub fn clear<T: MutableLoadOrder>(load_order: &mut T) -> Result<(), Error> {
+    load_order.plugins_mut().clear();
+
+    load_order.add_implicitly_active_plugins()
+}
+
+pub fn import_from<T: MutableLoadOrder>(
+    load_order: &mut T,
+    plugin_names: &[&str],
+) -> Result<Vec<String>, Error> {
+    let (installed, skipped): (Vec<&str>, Vec<&str>) = plugin_names.iter().partition(|n| {
+        load_order.index_of(n).is_some() || Plugin::new(n, load_order.game_settings()).is_ok()
+    });
+
+    load_order.replace_plugins(&installed)?;
+    load_order.add_implicitly_active_plugins()?;
+
+    Ok(skipped.into_iter().map(str::to_string).collect())
+}
+
+pub fn resolve_hoisting<T: MutableLoadOrder>(load_order: &mut T) -> Result<(), Error> {
+    hoist_masters(load_order.plugins_mut())
+}
+
+pub fn swap_plugins<T: MutableLoadOrder>(
+    load_order: &mut T,
+    plugin_name_a: &str,
+    plugin_name_b: &str,
+) -> Result<(), Error> {
+    let index_a = load_order
+        .index_of(plugin_name_a)
+        .ok_or_else(|| Error::PluginNotFound(plugin_name_a.to_string()))?;
+    let index_b = load_order
+        .index_of(plugin_name_b)
+        .ok_or_else(|| Error::PluginNotFound(plugin_name_b.to_string()))?;
+
+    let mut plugins = load_order.plugins().to_vec();
+    plugins.swap(index_a, index_b);
+
+    validate_game_master_position(&plugins, load_order.game_settings())?;
+
+    validate_load_order(&plugins, load_order.game_settings().early_loading_plugins())?;
+
+    load_order.plugins_mut().swap(index_a, index_b);
+
+    Ok(())
 }
 
 pub fn activate<T: MutableLoadOrder>(load_order: &mut T, plugin_name: &str) -> Result<(), Error> {
@@ -200,26 +345,64 @@ pub fn activate<T: MutableLoadOrder>(load_order: &mut T, plugin_name: &str) -> R
         None => return Err(Error::PluginNotFound(plugin_name.to_string())),
     };
 
-    if !plugin.is_active() {
-        let is_light = plugin.is_light_plugin();
-        let is_medium = plugin.is_medium_plugin();
-        let is_full = !is_light && !is_medium;
+    if activation_exceeds_limit(&counts, plugin) {
+        Err(Error::TooManyActivePlugins {
+            light_count: counts.light,
+            medium_count: counts.medium,
+            full_count: counts.full,
+        })
+    } else if !plugin.is_active() {
+        plugin.activate()
+    } else {
+        Ok(())
+    }
+}
 
-        if (is_light && counts.light == MAX_ACTIVE_LIGHT_PLUGINS)
-            || (is_medium && counts.medium == MAX_ACTIVE_MEDIUM_PLUGINS)
-            || (is_full && counts.full == counts.max_active_full_plugins())
-        {
-            return Err(Error::TooManyActivePlugins {
-                light_count: counts.light,
-                medium_count: counts.medium,
-                full_count: counts.full,
-            });
-        } else {
+pub fn activate_all_possible<T: MutableLoadOrder>(
+    load_order: &mut T,
+) -> Result<Vec<String>, Error> {
+    let mut counts = count_active_plugins(load_order);
+    let mut not_activated = Vec::new();
+
+    for plugin in load_order.plugins_mut() {
+        if activation_exceeds_limit(&counts, plugin) {
+            not_activated.push(plugin.name().to_string());
+        } else if !plugin.is_active() {
             plugin.activate()?;
+            counts.count_plugin(plugin);
         }
     }
 
-    Ok(())
+    Ok(not_activated)
+}
+
+pub fn would_exceed_limit<T: ReadableLoadOrderBase>(
+    load_order: &T,
+    plugin_name: &str,
+) -> Result<bool, Error> {
+    let plugin = load_order
+        .plugins()
+        .iter()
+        .find(|p| p.name_matches(plugin_name))
+        .ok_or_else(|| Error::PluginNotFound(plugin_name.to_string()))?;
+
+    Ok(activation_exceeds_limit(
+        &count_active_plugins(load_order),
+        plugin,
+    ))
+}
+
+fn activation_exceeds_limit(counts: &PluginCounts, plugin: &Plugin) -> bool {
+    if plugin.is_active() {
+        return false;
+    }
+
+    // Activating the first light or medium plugin lowers the full plugin
+    // limit, so check the counts as they'd be after activation.
+    let mut counts = *counts;
+    counts.count_plugin(plugin);
+
+    counts.exceeds_limits()
 }
 
 pub fn deactivate<T: MutableLoadOrder>(load_order: &mut T, plugin_name: &str) -> Result<(), Error> {
@@ -235,6 +418,26 @@ pub fn deactivate<T: MutableLoadOrder>(load_order: &mut T, plugin_name: &str) ->
         .map(|p| p.deactivate())
 }
 
+pub fn deactivate_plugins<T: MutableLoadOrder>(
+    load_order: &mut T,
+    plugin_names: &[&str],
+) -> Result<(), Error> {
+    if let Some(plugin_name) = plugin_names
+        .iter()
+        .find(|n| load_order.game_settings().is_implicitly_active(n))
+    {
+        return Err(Error::ImplicitlyActivePlugin(plugin_name.to_string()));
+    }
+
+    let plugin_indices = load_order.lookup_plugins(plugin_names)?;
+
+    for index in plugin_indices {
+        load_order.plugins_mut()[index].deactivate();
+    }
+
+    Ok(())
+}
+
 pub fn set_active_plugins<T: MutableLoadOrder>(
     load_order: &mut T,
     active_plugin_names: &[&str],
@@ -243,10 +446,7 @@ pub fn set_active_plugins<T: MutableLoadOrder>(
 
     let counts = count_plugins(load_order.plugins(), &existing_plugin_indices);
 
-    if counts.full > counts.max_active_full_plugins()
-        || counts.medium > MAX_ACTIVE_MEDIUM_PLUGINS
-        || counts.light > MAX_ACTIVE_LIGHT_PLUGINS
-    {
+    if counts.exceeds_limits() {
         return Err(Error::TooManyActivePlugins {
             light_count: counts.light,
             medium_count: counts.medium,
@@ -273,6 +473,295 @@ pub fn set_active_plugins<T: MutableLoadOrder>(
     Ok(())
 }
 
+pub fn set_active_plugins_ordered<T: MutableLoadOrder>(
+    load_order: &mut T,
+    active_plugin_names: &[&str],
+) -> Result<(), Error> {
+    let existing_plugin_indices = load_order.lookup_plugins(active_plugin_names)?;
+
+    let mut unique_indices = HashSet::new();
+    for (index, name) in existing_plugin_indices.iter().zip(active_plugin_names) {
+        if !unique_indices.insert(*index) {
+            return Err(Error::DuplicatePlugin(name.to_string()));
+        }
+    }
+
+    let plugins = reorder_plugins(load_order.plugins(), &existing_plugin_indices);
+    validate_game_master_position(&plugins, load_order.game_settings())?;
+    validate_load_order(&plugins, load_order.game_settings().early_loading_plugins())?;
+
+    set_active_plugins(load_order, active_plugin_names)?;
+
+    let plugins = reorder_plugins(load_order.plugins(), &existing_plugin_indices);
+    *load_order.plugins_mut() = plugins;
+
+    Ok(())
+}
+
+/// Move the plugins at the given indices into the given order, using the
+/// positions that they already occupy between them.
+fn reorder_plugins(plugins: &[Plugin], ordered_indices: &[usize]) -> Vec<Plugin> {
+    let mut positions = ordered_indices.to_vec();
+    positions.sort_unstable();
+
+    let mut reordered = plugins.to_vec();
+    for (position, index) in positions.into_iter().zip(ordered_indices) {
+        reordered[position] = plugins[*index].clone();
+    }
+
+    reordered
+}
+
+pub fn set_active_plugins_reporting<T: MutableLoadOrder>(
+    load_order: &mut T,
+    active_plugin_names: &[&str],
+) -> Result<ActivePluginCounts, Error> {
+    set_active_plugins(load_order, active_plugin_names)?;
+
+    Ok(count_active_plugins(load_order).into())
+}
+
+pub fn to_state<T: ReadableLoadOrderBase>(load_order: &T) -> Vec<(String, bool)> {
+    load_order
+        .plugins()
+        .iter()
+        .map(|p| (p.name().to_string(), p.is_active()))
+        .collect()
+}
+
+pub fn from_state<T: MutableLoadOrder>(
+    load_order: &mut T,
+    state: &[(String, bool)],
+) -> Result<(), Error> {
+    let plugin_names: Vec<&str> = state.iter().map(|(n, _)| n.as_str()).collect();
+    let active_plugin_names: Vec<&str> = state
+        .iter()
+        .filter(|(_, is_active)| *is_active)
+        .map(|(n, _)| n.as_str())
+        .collect();
+
+    let previous_plugins = load_order.plugins().to_vec();
+
+    let result = load_order
+        .replace_plugins(&plugin_names)
+        .and_then(|_| set_active_plugins(load_order, &active_plugin_names));
+
+    if result.is_err() {
+        *load_order.plugins_mut() = previous_plugins;
+    }
+
+    result
+}
+
+pub fn detect_active_plugins_file_method_mismatch(
+    game_settings: &GameSettings,
+) -> Result<Option<LoadOrderMethod>, Error> {
+    // An asterisk-based file with no active plugins is indistinguishable from
+    // a list of inactive plugins, so only asterisks can be detected.
+    if game_settings.load_order_method() == LoadOrderMethod::Asterisk {
+        return Ok(None);
+    }
+
+    let path = game_settings.active_plugins_file();
+    let asterisk_lines = read_plugin_names(path, |line| line.starts_with('*').then_some(()))?;
+
+    if asterisk_lines.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(LoadOrderMethod::Asterisk))
+    }
+}
+
+pub fn active_plugins_missing_from_load_order_file<T: ReadableLoadOrderBase>(
+    load_order: &T,
+) -> Result<Vec<String>, Error> {
+    let path = match load_order.game_settings().load_order_file() {
+        Some(path) => path,
+        None => return Ok(Vec::new()),
+    };
+
+    let listed_plugin_names: HashSet<_> = read_utf8_plugin_names(path, plugin_line_mapper)
+        .or_else(|_| read_plugin_names(path, plugin_line_mapper))?
+        .into_iter()
+        .map(|n| UniCase::new(trim_dot_ghost(&n).to_string()))
+        .collect();
+
+    Ok(load_order
+        .active_plugin_names()
+        .into_iter()
+        .filter(|n| !listed_plugin_names.contains(&UniCase::new(n.to_string())))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Apply the configured action for active plugins in excess of the game's
+/// limits, before the load order is saved.
+pub fn handle_excess_active_plugins<T: MutableLoadOrder>(load_order: &mut T) -> Result<(), Error> {
+    let excess_plugins = load_order.excess_active_plugins();
+    if excess_plugins.is_empty() {
+        return Ok(());
+    }
+
+    match load_order.game_settings().excess_active_plugins_on_save() {
+        ExcessActivePluginsAction::Keep => Ok(()),
+        ExcessActivePluginsAction::Deactivate => {
+            for plugin in load_order
+                .plugins_mut()
+                .iter_mut()
+                .filter(|p| excess_plugins.iter().any(|e| p.name_matches(e)))
+            {
+                plugin.deactivate();
+            }
+            Ok(())
+        }
+        ExcessActivePluginsAction::Reject => {
+            let counts = count_active_plugins(load_order);
+            Err(Error::TooManyActivePlugins {
+                light_count: counts.light,
+                medium_count: counts.medium,
+                full_count: counts.full,
+            })
+        }
+    }
+}
+
+pub fn save_with_backup<T: WritableLoadOrder>(load_order: &mut T) -> Result<(), Error> {
+    let game_settings = load_order.game_settings();
+    let max_backups = game_settings.max_backups();
+
+    back_up_file(game_settings.active_plugins_file(), max_backups)?;
+    if let Some(path) = game_settings.load_order_file() {
+        back_up_file(path, max_backups)?;
+    }
+
+    load_order.save()
+}
+
+/// Copy the given file to a timestamped ".bak" file next to it, then delete
+/// the oldest of its backups so that no more than max_backups remain. No
+/// backup is made if max_backups is zero.
+fn back_up_file(path: &Path, max_backups: usize) -> Result<(), Error> {
+    let (parent, filename) = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(filename)) if max_backups > 0 && path.exists() => (parent, filename),
+        _ => return Ok(()),
+    };
+
+    let prefix = format!("{}.", filename.to_string_lossy());
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    let backup_path = parent.join(format!("{prefix}{timestamp:020}.bak"));
+    copy(path, &backup_path).map_err(|e| Error::IoError(backup_path.clone(), e))?;
+
+    let mut backups: Vec<_> = read_dir(parent)
+        .map_err(|e| Error::IoError(parent.to_path_buf(), e))?
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&prefix) && n.ends_with(".bak"))
+        })
+        .collect();
+
+    // The timestamps are zero-padded, so sorting by name sorts oldest first.
+    backups.sort();
+
+    let excess_count = backups.len().saturating_sub(max_backups);
+    for backup in &backups[..excess_count] {
+        remove_file(backup).map_err(|e| Error::IoError(backup.clone(), e))?;
+    }
+
+    Ok(())
+}
+
+/// The load order methods that each game's load order can be saved using: its
+/// own method, plus the methods of the games it's commonly migrated to or
+/// from. Skyrim can also read a timestamp-based load order, as it falls back
+/// to timestamps for plugins that aren't listed in loadorder.txt, and its
+/// plugins.txt is where Skyrim Special Edition and Skyrim VR look for an
+/// asterisk-based load order. Those games can likewise be migrated back to
+/// Skyrim's textfile-based load order.
+fn supported_save_methods(game_id: GameId) -> &'static [LoadOrderMethod] {
+    use crate::enums::GameId::*;
+    match game_id {
+        Morrowind | Oblivion | Fallout3 | FalloutNV => &[LoadOrderMethod::Timestamp],
+        Skyrim => &[
+            LoadOrderMethod::Textfile,
+            LoadOrderMethod::Timestamp,
+            LoadOrderMethod::Asterisk,
+        ],
+        SkyrimSE | SkyrimVR => &[LoadOrderMethod::Asterisk, LoadOrderMethod::Textfile],
+        Fallout4 | Fallout4VR | Starfield => &[LoadOrderMethod::Asterisk],
+    }
+}
+
+pub fn save_as_method<T: MutableLoadOrder>(
+    load_order: &mut T,
+    method: LoadOrderMethod,
+) -> Result<(), Error> {
+    let game_id = load_order.game_settings().id();
+    if !supported_save_methods(game_id).contains(&method) {
+        return Err(Error::IncompatibleLoadOrderMethod { game_id, method });
+    }
+
+    handle_excess_active_plugins(load_order)?;
+
+    match method {
+        LoadOrderMethod::Timestamp => save_using_timestamps(load_order),
+        LoadOrderMethod::Textfile => {
+            // Games that don't use the textfile-based method have no
+            // loadorder.txt of their own, so write it where Skyrim keeps it,
+            // alongside plugins.txt.
+            let game_settings = load_order.game_settings();
+            let load_order_file = match game_settings.load_order_file() {
+                Some(path) => path.clone(),
+                None => game_settings
+                    .active_plugins_file()
+                    .with_file_name("loadorder.txt"),
+            };
+            save_load_order(load_order, &load_order_file)?;
+            save_active_plugins(load_order)
+        }
+        LoadOrderMethod::Asterisk => save_using_asterisks(load_order),
+    }
+}
+
+/// Create or truncate the active plugins file, reporting if another process
+/// (e.g. the running game) is preventing it from being written.
+pub fn create_active_plugins_file(path: &Path) -> Result<File, Error> {
+    File::create(path).map_err(|e| {
+        if is_sharing_violation(&e) {
+            Error::ActivePluginsFileLocked(path.to_path_buf())
+        } else {
+            Error::IoError(path.to_path_buf(), e)
+        }
+    })
+}
+
+/// Write the given serialized content to the active plugins file, creating
+/// its parent directories if necessary.
+pub fn write_active_plugins(path: &Path, content: &[u8]) -> Result<(), Error> {
+    create_parent_dirs(path)?;
+
+    let mut file = create_active_plugins_file(path)?;
+    file.write_all(content)
+        .map_err(|e| Error::IoError(path.to_path_buf(), e))
+}
+
+#[cfg(windows)]
+fn is_sharing_violation(error: &io::Error) -> bool {
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    error.raw_os_error() == Some(ERROR_SHARING_VIOLATION)
+}
+
+#[cfg(not(windows))]
+fn is_sharing_violation(_error: &io::Error) -> bool {
+    false
+}
+
 pub fn create_parent_dirs(path: &Path) -> Result<(), Error> {
     if let Some(x) = path.parent() {
         if !x.exists() {
@@ -295,9 +784,14 @@ mod tests {
     use crate::enums::GameId;
     use crate::game_settings::GameSettings;
     use crate::load_order::mutable::MutableLoadOrder;
-    use crate::load_order::readable::{ReadableLoadOrder, ReadableLoadOrderBase};
+    use crate::load_order::readable::{
+        ReadableLoadOrder, ReadableLoadOrderBase, MAX_ACTIVE_FULL_PLUGINS, MAX_ACTIVE_LIGHT_PLUGINS,
+    };
     use crate::load_order::tests::{
-        load_and_insert, mock_game_files, set_blueprint_flag, set_master_flag,
+        load_and_insert, mock_game_files, set_blueprint_flag, set_master_flag, to_owned,
+    };
+    use crate::load_order::{
+        AsteriskBasedLoadOrder, TextfileBasedLoadOrder, TimestampBasedLoadOrder,
     };
     use crate::tests::copy_to_test_dir;
 
@@ -330,6 +824,24 @@ mod tests {
         }
     }
 
+    fn load_order_from_plugins(
+        game_settings: GameSettings,
+        plugins: Vec<Plugin>,
+    ) -> Box<dyn WritableLoadOrder> {
+        match game_settings.load_order_method() {
+            LoadOrderMethod::Asterisk => {
+                Box::new(AsteriskBasedLoadOrder::from_plugins(game_settings, plugins))
+            }
+            LoadOrderMethod::Textfile => {
+                Box::new(TextfileBasedLoadOrder::from_plugins(game_settings, plugins))
+            }
+            LoadOrderMethod::Timestamp => Box::new(TimestampBasedLoadOrder::from_plugins(
+                game_settings,
+                plugins,
+            )),
+        }
+    }
+
     fn insert<T: MutableLoadOrder>(load_order: &mut T, plugin: Plugin) {
         match load_order.insert_position(&plugin) {
             Some(position) => {
@@ -616,6 +1128,177 @@ mod tests {
         assert!(load_order.index_of("Blank.esp").is_none());
     }
 
+    #[test]
+    fn apply_sorted_order_should_error_if_given_a_plugin_not_in_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esm", load_order.game_settings());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        let filenames = vec!["Oblivion.esm", "Blank.esm", "Blank.esp"];
+        match apply_sorted_order(&mut load_order, &filenames).unwrap_err() {
+            Error::PluginNotFound(name) => assert_eq!("Blank.esm", name),
+            e => panic!("Expected plugin not found error, got {:?}", e),
+        }
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn apply_sorted_order_should_reorder_plugins_and_preserve_their_active_states() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let filenames = vec!["Oblivion.esm", "Blank - Different.esp", "Blank.esp"];
+        apply_sorted_order(&mut load_order, &filenames).unwrap();
+
+        assert_eq!(filenames, load_order.plugin_names());
+        assert_eq!(vec!["Blank.esp"], load_order.active_plugin_names());
+    }
+
+    #[test]
+    fn apply_sorted_order_should_keep_unlisted_plugins_after_the_listed_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let filenames = vec!["Oblivion.esm", "Blank - Different.esp"];
+        apply_sorted_order(&mut load_order, &filenames).unwrap();
+
+        assert_eq!(
+            vec!["Oblivion.esm", "Blank - Different.esp", "Blank.esp"],
+            load_order.plugin_names()
+        );
+        assert!(load_order.is_active("Blank.esp"));
+    }
+
+    #[test]
+    fn clear_should_leave_only_the_implicitly_active_plugins_active() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        load_and_insert(&mut load_order, "Blank.esm");
+        activate(&mut load_order, "Blank.esm").unwrap();
+
+        clear(&mut load_order).unwrap();
+
+        assert_eq!(vec!["Skyrim.esm"], load_order.plugin_names());
+        assert_eq!(vec!["Skyrim.esm"], load_order.active_plugin_names());
+    }
+
+    #[test]
+    fn resolve_hoisting_should_move_masters_before_the_master_files_that_depend_on_them() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let dependent_master = "Blank - Master Dependent.esm";
+        copy_to_test_dir(
+            dependent_master,
+            dependent_master,
+            load_order.game_settings(),
+        );
+
+        load_order.plugins = ["Skyrim.esm", dependent_master, "Blank.esm", "Blank.esp"]
+            .iter()
+            .map(|n| Plugin::new(n, load_order.game_settings()).unwrap())
+            .collect();
+
+        match load_order.check_hoisting().unwrap_err() {
+            Error::UnrepresentedHoist { plugin, master } => {
+                assert_eq!("Blank.esm", plugin);
+                assert_eq!(dependent_master, master);
+            }
+            e => panic!("Expected unrepresented hoist error, got {:?}", e),
+        }
+
+        resolve_hoisting(&mut load_order).unwrap();
+
+        assert!(load_order.check_hoisting().is_ok());
+        assert_eq!(
+            vec!["Skyrim.esm", "Blank.esm", dependent_master, "Blank.esp"],
+            load_order.plugin_names()
+        );
+    }
+
+    #[test]
+    fn swap_plugins_should_error_if_a_plugin_is_not_in_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        match swap_plugins(&mut load_order, "Blank.esp", "missing.esp").unwrap_err() {
+            Error::PluginNotFound(name) => assert_eq!("missing.esp", name),
+            e => panic!("Expected plugin not found error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn swap_plugins_should_swap_the_positions_of_the_given_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        swap_plugins(&mut load_order, "Blank - Different.esp", "Blank.esp").unwrap();
+
+        assert_eq!(
+            vec!["Oblivion.esm", "Blank - Different.esp", "Blank.esp"],
+            load_order.plugin_names()
+        );
+        assert!(load_order.is_active("Blank.esp"));
+    }
+
+    #[test]
+    fn swap_plugins_should_error_if_a_non_master_would_load_before_a_master() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let existing_filenames = to_owned(load_order.plugin_names());
+        match swap_plugins(&mut load_order, "Oblivion.esm", "Blank.esp").unwrap_err() {
+            Error::NonMasterBeforeMaster { master, non_master } => {
+                assert_eq!("Oblivion.esm", master);
+                assert_eq!("Blank.esp", non_master);
+            }
+            e => panic!("Expected non-master before master error, got {:?}", e),
+        }
+        assert_eq!(existing_filenames, load_order.plugin_names());
+    }
+
+    #[test]
+    fn from_state_should_round_trip_the_output_of_to_state() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let mut state = to_state(&load_order);
+        assert_eq!(
+            vec![
+                ("Oblivion.esm".to_string(), false),
+                ("Blank.esp".to_string(), true),
+                ("Blank - Different.esp".to_string(), false),
+            ],
+            state
+        );
+
+        state.swap(1, 2);
+        state[1].1 = true;
+        state[2].1 = false;
+        from_state(&mut load_order, &state).unwrap();
+
+        assert_eq!(state, to_state(&load_order));
+    }
+
+    #[test]
+    fn from_state_should_leave_the_load_order_unchanged_if_the_active_plugins_are_invalid() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimSE, &tmp_dir.path());
+
+        let previous_state = to_state(&load_order);
+
+        // Skyrim.esm is implicitly active, so can't be deactivated.
+        let mut state = previous_state.clone();
+        state.swap(1, 2);
+        state[0].1 = false;
+        assert!(from_state(&mut load_order, &state).is_err());
+
+        assert_eq!(previous_state, to_state(&load_order));
+    }
+
     #[test]
     fn activate_should_activate_the_plugin_with_the_given_filename() {
         let tmp_dir = tempdir().unwrap();
@@ -844,6 +1527,149 @@ mod tests {
         assert!(!load_order.is_active(plugin));
     }
 
+    #[test]
+    fn activate_all_possible_should_activate_plugins_in_order_up_to_the_limit() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let plugins = prepare_bulk_full_plugins(&mut load_order);
+        let not_activated = activate_all_possible(&mut load_order).unwrap();
+
+        let active_count = load_order
+            .plugins()
+            .iter()
+            .filter(|p| p.is_active())
+            .count();
+        assert_eq!(MAX_ACTIVE_FULL_PLUGINS, active_count);
+
+        let expected_count = load_order.plugins().len() - MAX_ACTIVE_FULL_PLUGINS;
+        assert_eq!(expected_count, not_activated.len());
+        assert!(not_activated.contains(plugins.last().unwrap()));
+        assert!(not_activated.iter().all(|n| !load_order.is_active(n)));
+        assert!(load_order.is_active(load_order.game_settings().master_file()));
+    }
+
+    #[test]
+    fn activate_all_possible_should_not_activate_a_light_plugin_if_that_would_lower_the_full_plugin_limit_below_the_active_count(
+    ) {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Starfield, &tmp_dir.path());
+
+        let full = prepare_bulk_full_plugins(&mut load_order);
+        let mut plugin_refs = vec!["Starfield.esm"];
+        plugin_refs.extend(
+            full[..MAX_ACTIVE_FULL_PLUGINS - 1]
+                .iter()
+                .map(String::as_str),
+        );
+        set_active_plugins(&mut load_order, &plugin_refs).unwrap();
+
+        let light = "Blank.small.esm";
+        load_and_insert(&mut load_order, light);
+
+        assert!(would_exceed_limit(&load_order, light).unwrap());
+
+        let not_activated = activate_all_possible(&mut load_order).unwrap();
+
+        assert!(not_activated.iter().any(|n| n == light));
+        assert!(!load_order.is_active(light));
+        assert_eq!(
+            MAX_ACTIVE_FULL_PLUGINS,
+            count_active_plugins(&load_order).full
+        );
+        assert!(load_order.excess_active_plugins().is_empty());
+    }
+
+    #[test]
+    fn would_exceed_limit_should_error_if_the_plugin_is_not_in_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        match would_exceed_limit(&load_order, "missing.esp").unwrap_err() {
+            Error::PluginNotFound(name) => assert_eq!("missing.esp", name),
+            e => panic!("Expected plugin not found error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn would_exceed_limit_should_be_true_iff_activate_would_fail_at_the_full_plugin_limit() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        let plugins = prepare_bulk_full_plugins(&mut load_order);
+        for plugin in &plugins[..MAX_ACTIVE_FULL_PLUGINS - 2] {
+            activate(&mut load_order, &plugin).unwrap();
+        }
+
+        assert!(!would_exceed_limit(&load_order, "Blank - Different.esp").unwrap());
+
+        activate(&mut load_order, &plugins[MAX_ACTIVE_FULL_PLUGINS - 2]).unwrap();
+
+        assert!(would_exceed_limit(&load_order, "Blank - Different.esp").unwrap());
+        assert!(!would_exceed_limit(&load_order, "Blank.esp").unwrap());
+        assert!(activate(&mut load_order, "Blank - Different.esp").is_err());
+    }
+
+    #[test]
+    fn would_exceed_limit_should_check_the_light_plugin_limit_for_light_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Starfield, &tmp_dir.path());
+
+        let light = prepare_bulk_light_plugins(&mut load_order);
+        for plugin in &light[..MAX_ACTIVE_LIGHT_PLUGINS - 1] {
+            activate(&mut load_order, plugin).unwrap();
+        }
+
+        let plugin = &light[MAX_ACTIVE_LIGHT_PLUGINS - 1];
+        assert!(!would_exceed_limit(&load_order, plugin).unwrap());
+        activate(&mut load_order, plugin).unwrap();
+
+        let plugin = &light[MAX_ACTIVE_LIGHT_PLUGINS];
+        assert!(would_exceed_limit(&load_order, plugin).unwrap());
+        assert!(activate(&mut load_order, plugin).is_err());
+    }
+
+    #[test]
+    fn deactivate_plugins_should_deactivate_all_the_given_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        activate(&mut load_order, "Blank - Different.esp").unwrap();
+
+        deactivate_plugins(&mut load_order, &["Blank.esp", "blank - different.esp"]).unwrap();
+
+        assert!(load_order.active_plugin_names().is_empty());
+    }
+
+    #[test]
+    fn deactivate_plugins_should_not_deactivate_anything_if_a_plugin_is_implicitly_active() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Skyrim, &tmp_dir.path());
+
+        activate(&mut load_order, "Skyrim.esm").unwrap();
+
+        match deactivate_plugins(&mut load_order, &["Blank.esp", "Skyrim.esm"]).unwrap_err() {
+            Error::ImplicitlyActivePlugin(name) => assert_eq!("Skyrim.esm", name),
+            e => panic!("Expected implicitly active plugin error, got {:?}", e),
+        }
+
+        assert!(load_order.is_active("Blank.esp"));
+        assert!(load_order.is_active("Skyrim.esm"));
+    }
+
+    #[test]
+    fn deactivate_plugins_should_not_deactivate_anything_if_a_plugin_is_not_in_the_load_order() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        match deactivate_plugins(&mut load_order, &["Blank.esp", "missing.esp"]).unwrap_err() {
+            Error::PluginNotFound(name) => assert_eq!("missing.esp", name),
+            e => panic!("Expected plugin not found error, got {:?}", e),
+        }
+
+        assert!(load_order.is_active("Blank.esp"));
+    }
+
     #[test]
     fn deactivate_should_deactivate_the_plugin_with_the_given_filename() {
         let tmp_dir = tempdir().unwrap();
@@ -960,6 +1786,28 @@ mod tests {
         assert!(load_order.is_active("Blank - Different.esp"));
     }
 
+    #[test]
+    fn set_active_plugins_reporting_should_return_the_resulting_active_plugin_counts() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Starfield, &tmp_dir.path());
+
+        load_and_insert(&mut load_order, "Blank.medium.esm");
+        load_and_insert(&mut load_order, "Blank.small.esm");
+
+        let active_plugins = ["Starfield.esm", "Blank.medium.esm", "Blank.small.esm"];
+        let counts = set_active_plugins_reporting(&mut load_order, &active_plugins).unwrap();
+
+        assert_eq!(load_order.active_plugin_counts(), counts);
+        assert_eq!(
+            ActivePluginCounts {
+                full: 1,
+                medium: 1,
+                light: 1,
+            },
+            counts
+        );
+    }
+
     #[test]
     fn set_active_plugins_should_count_update_plugins_towards_limit() {
         let tmp_dir = tempdir().unwrap();
@@ -1081,6 +1929,42 @@ mod tests {
         assert_eq!(1, load_order.active_plugin_names().len());
     }
 
+    #[test]
+    fn set_active_plugins_ordered_should_activate_and_reorder_the_given_plugins() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        load_and_insert(&mut load_order, "Blank - Master Dependent.esp");
+
+        let active_plugins = ["Blank - Master Dependent.esp", "Blank.esp"];
+        set_active_plugins_ordered(&mut load_order, &active_plugins).unwrap();
+
+        assert_eq!(active_plugins.to_vec(), load_order.active_plugin_names());
+        assert_eq!(
+            vec![
+                "Oblivion.esm",
+                "Blank - Master Dependent.esp",
+                "Blank - Different.esp",
+                "Blank.esp",
+            ],
+            load_order.plugin_names()
+        );
+    }
+
+    #[test]
+    fn set_active_plugins_ordered_should_not_change_anything_if_the_order_is_invalid() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::Oblivion, &tmp_dir.path());
+
+        load_and_insert(&mut load_order, "Blank.esm");
+        let previous_state = to_state(&load_order);
+
+        let active_plugins = ["Blank.esp", "Blank.esm"];
+        assert!(set_active_plugins_ordered(&mut load_order, &active_plugins).is_err());
+
+        assert_eq!(previous_state, to_state(&load_order));
+    }
+
     #[test]
     fn set_active_plugins_should_error_if_given_more_than_256_medium_plugins() {
         let tmp_dir = tempdir().unwrap();
@@ -1108,4 +1992,92 @@ mod tests {
         assert!(set_active_plugins(&mut load_order, &plugin_refs).is_err());
         assert_eq!(1, load_order.active_plugin_names().len());
     }
+
+    #[test]
+    fn set_active_plugins_should_count_skyrim_vr_light_plugins_against_the_light_plugin_limit() {
+        let tmp_dir = tempdir().unwrap();
+        let mut load_order = prepare(GameId::SkyrimVR, &tmp_dir.path());
+
+        let light = prepare_bulk_plugins(&mut load_order, "Blank.esm", 4097, |i| {
+            format!("Blank{}.esl", i)
+        });
+
+        let mut plugin_refs = vec!["Skyrim.esm"];
+        plugin_refs.extend(light[..4096].iter().map(String::as_str));
+
+        assert!(set_active_plugins(&mut load_order, &plugin_refs).is_ok());
+        assert_eq!(4097, load_order.active_plugin_names().len());
+
+        plugin_refs.push(light[4096].as_str());
+
+        match set_active_plugins(&mut load_order, &plugin_refs).unwrap_err() {
+            Error::TooManyActivePlugins {
+                light_count,
+                medium_count,
+                full_count,
+            } => {
+                assert_eq!(4097, light_count);
+                assert_eq!(0, medium_count);
+                assert_eq!(1, full_count);
+            }
+            e => panic!("Expected too many active plugins error, got {:?}", e),
+        }
+        assert_eq!(4097, load_order.active_plugin_names().len());
+    }
+
+    #[test]
+    fn from_plugins_should_not_need_the_plugins_directory_to_exist() {
+        for game_id in [GameId::Oblivion, GameId::Skyrim, GameId::SkyrimSE] {
+            let tmp_dir = tempdir().unwrap();
+            let (game_settings, plugins) = mock_game_files(game_id, &tmp_dir.path());
+            tmp_dir.close().unwrap();
+
+            let master_file = game_settings.master_file();
+            let load_order = load_order_from_plugins(game_settings, plugins);
+
+            let expected_filenames = vec![master_file, "Blank.esp", "Blank - Different.esp"];
+            assert_eq!(expected_filenames, load_order.plugin_names());
+            assert_eq!(vec!["Blank.esp"], load_order.active_plugin_names());
+            assert_eq!(1, load_order.index_of("Blank.esp").unwrap());
+            assert!(load_order.is_active("Blank.esp"));
+            assert!(load_order.is_self_consistent().unwrap());
+        }
+    }
+
+    #[test]
+    fn set_load_order_should_not_change_the_plugins_if_given_the_current_order() {
+        for game_id in [GameId::Oblivion, GameId::Skyrim, GameId::SkyrimSE] {
+            let tmp_dir = tempdir().unwrap();
+            let (game_settings, plugins) = mock_game_files(game_id, &tmp_dir.path());
+            let mut load_order = load_order_from_plugins(game_settings, plugins);
+
+            let plugins_ptr = load_order.loaded_plugins().as_ptr();
+            let timestamps: Vec<_> = load_order
+                .loaded_plugins()
+                .iter()
+                .map(Plugin::modification_time)
+                .collect();
+
+            let filenames = to_owned(load_order.plugin_names());
+            let filenames: Vec<&str> = filenames.iter().map(String::as_str).collect();
+            load_order.set_load_order(&filenames).unwrap();
+
+            let last_plugin = *filenames.last().unwrap();
+            let last_index = filenames.len() - 1;
+            assert_eq!(
+                last_index,
+                load_order
+                    .set_plugin_index(last_plugin, last_index + 1)
+                    .unwrap()
+            );
+
+            assert_eq!(plugins_ptr, load_order.loaded_plugins().as_ptr());
+            let new_timestamps: Vec<_> = load_order
+                .loaded_plugins()
+                .iter()
+                .map(Plugin::modification_time)
+                .collect();
+            assert_eq!(timestamps, new_timestamps);
+        }
+    }
 }
@@ -18,8 +18,9 @@ use std::ffi::OsStr;
  * along with libloadorder. If not, see <http://www.gnu.org/licenses/>.
  */
 use std::fs::{File, FileTimes};
-use std::path::Path;
-use std::time::SystemTime;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use esplugin::ParseOptions;
 use unicase::eq;
@@ -68,6 +69,34 @@ impl Plugin {
         Plugin::with_path(&filepath, game_settings.id(), active)
     }
 
+    /// Parse a plugin's header from data that has already been read into
+    /// memory (e.g. from an archive), without reading anything from disk.
+    ///
+    /// As there is no file to take a timestamp from, the plugin's modification
+    /// time is set to the Unix epoch.
+    pub fn from_bytes(
+        filename: &str,
+        bytes: &[u8],
+        game_settings: &GameSettings,
+    ) -> Result<Plugin, Error> {
+        if !has_plugin_extension(filename, game_settings.id()) {
+            return Err(Error::InvalidPath(PathBuf::from(filename)));
+        }
+
+        let path = game_settings.plugin_path(filename);
+
+        let mut data = esplugin::Plugin::new(game_settings.id().to_esplugin_id(), &path);
+        data.parse_reader(Cursor::new(bytes), ParseOptions::header_only())
+            .map_err(|e| file_error(&path, e))?;
+
+        Ok(Plugin {
+            active: false,
+            modification_time: UNIX_EPOCH,
+            data,
+            name: trim_dot_ghost(filename).to_string(),
+        })
+    }
+
     pub(crate) fn with_path(path: &Path, game_id: GameId, active: bool) -> Result<Plugin, Error> {
         let filename = match path.file_name().and_then(OsStr::to_str) {
             Some(n) => n,
@@ -108,6 +137,16 @@ impl Plugin {
         self.modification_time
     }
 
+    /// Read the plugin file's current modification time, which may differ
+    /// from the cached value if the file has been changed externally.
+    pub(crate) fn modification_time_on_disk(&self) -> Result<SystemTime, Error> {
+        self.data
+            .path()
+            .metadata()
+            .and_then(|m| m.modified())
+            .map_err(|e| Error::IoError(self.data.path().to_path_buf(), e))
+    }
+
     pub fn is_active(&self) -> bool {
         self.active
     }
@@ -124,6 +163,10 @@ impl Plugin {
         self.data.is_medium_plugin()
     }
 
+    pub fn is_update_plugin(&self) -> bool {
+        self.data.is_update_plugin()
+    }
+
     pub fn is_blueprint_master(&self) -> bool {
         self.is_master_file() && self.data.is_blueprint_plugin()
     }
@@ -134,7 +177,7 @@ impl Plugin {
             .map_err(|e| file_error(self.data.path(), e))
     }
 
-    pub fn set_modification_time(&mut self, time: SystemTime) -> Result<(), Error> {
+    pub(crate) fn set_modification_time(&mut self, time: SystemTime) -> Result<(), Error> {
         // Always write the file time. This has a huge performance impact, but
         // is important for correctness, as otherwise external changes to plugin
         // timestamps between calls to WritableLoadOrder::load() and
@@ -154,7 +197,7 @@ impl Plugin {
         Ok(())
     }
 
-    pub fn activate(&mut self) -> Result<(), Error> {
+    pub(crate) fn activate(&mut self) -> Result<(), Error> {
         if !self.is_active() {
             if self.data.path().is_ghosted() {
                 let new_path = self.data.path().unghost()?;
@@ -172,7 +215,7 @@ impl Plugin {
         Ok(())
     }
 
-    pub fn deactivate(&mut self) {
+    pub(crate) fn deactivate(&mut self) {
         self.active = false;
     }
 }
@@ -200,6 +243,12 @@ fn iends_with_ascii(string: &str, suffix: &str) -> bool {
             .all(|(string_byte, suffix_byte)| string_byte.eq_ignore_ascii_case(suffix_byte))
 }
 
+/// Remove surrounding whitespace and a leading byte order mark from a plugin
+/// filename, as hand-edited plugin lists may contain either.
+pub fn normalize_plugin_name(name: &str) -> &str {
+    name.trim_start_matches('\u{feff}').trim()
+}
+
 pub fn trim_dot_ghost(string: &str) -> &str {
     if iends_with_ascii(string, GHOST_FILE_EXTENSION) {
         &string[..(string.len() - GHOST_FILE_EXTENSION.len())]
@@ -222,7 +271,7 @@ mod tests {
 
     use crate::tests::copy_to_test_dir;
     use std::path::{Path, PathBuf};
-    use std::time::{Duration, UNIX_EPOCH};
+    use std::time::Duration;
     use tempfile::tempdir;
 
     fn game_settings(game_id: GameId, game_path: &Path) -> GameSettings {
@@ -255,6 +304,67 @@ mod tests {
         assert_eq!("Blank.esm", plugin.name());
     }
 
+    #[test]
+    fn from_bytes_should_parse_the_same_header_data_as_reading_from_disk() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = game_settings(GameId::SkyrimSE, tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esm", &settings);
+        copy_to_test_dir(
+            "Blank - Master Dependent.esp",
+            "Blank - Master Dependent.esp",
+            &settings,
+        );
+
+        for filename in ["Blank.esm", "Blank - Master Dependent.esp"] {
+            let file_plugin = Plugin::new(filename, &settings).unwrap();
+
+            let bytes = std::fs::read(settings.plugin_path(filename)).unwrap();
+            let plugin = Plugin::from_bytes(filename, &bytes, &settings).unwrap();
+
+            assert_eq!(file_plugin.name(), plugin.name());
+            assert_eq!(file_plugin.is_master_file(), plugin.is_master_file());
+            assert_eq!(file_plugin.is_light_plugin(), plugin.is_light_plugin());
+            assert_eq!(file_plugin.is_medium_plugin(), plugin.is_medium_plugin());
+            assert_eq!(file_plugin.masters().unwrap(), plugin.masters().unwrap());
+            assert!(!plugin.is_active());
+        }
+    }
+
+    #[test]
+    fn from_bytes_should_not_require_the_plugin_to_exist_on_disk() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = game_settings(GameId::Oblivion, tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esm", &settings);
+        let bytes = std::fs::read(settings.plugin_path("Blank.esm")).unwrap();
+
+        let plugin = Plugin::from_bytes("Missing.esm", &bytes, &settings).unwrap();
+
+        assert_eq!("Missing.esm", plugin.name());
+        assert!(plugin.is_master_file());
+        assert_eq!(UNIX_EPOCH, plugin.modification_time());
+    }
+
+    #[test]
+    fn from_bytes_should_error_if_the_filename_does_not_have_a_plugin_extension() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = game_settings(GameId::Oblivion, tmp_dir.path());
+
+        copy_to_test_dir("Blank.esm", "Blank.esm", &settings);
+        let bytes = std::fs::read(settings.plugin_path("Blank.esm")).unwrap();
+
+        assert!(Plugin::from_bytes("Blank.txt", &bytes, &settings).is_err());
+    }
+
+    #[test]
+    fn from_bytes_should_error_if_the_bytes_are_not_a_valid_plugin() {
+        let tmp_dir = tempdir().unwrap();
+        let settings = game_settings(GameId::Oblivion, tmp_dir.path());
+
+        assert!(Plugin::from_bytes("Blank.esp", b"not a plugin", &settings).is_err());
+    }
+
     #[test]
     fn name_matches_should_ignore_plugin_ghost_extension() {
         let tmp_dir = tempdir().unwrap();